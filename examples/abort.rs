@@ -0,0 +1,70 @@
+use flemish::{task::TaskHandle, view::*, Settings, Task};
+use std::time::Duration;
+
+pub fn main() {
+    flemish::application("abort", App::update, App::view)
+        .settings(Settings {
+            size: (300, 100),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct App {
+    running: Option<TaskHandle>,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Start,
+    Abort,
+    Done(u64),
+}
+
+impl App {
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Start => {
+                let (task, handle) = Task::perform_with_token(|token| {
+                    let mut steps = 0;
+                    while steps < 50 && !token.is_aborted() {
+                        std::thread::sleep(Duration::from_millis(100));
+                        steps += 1;
+                    }
+                    steps
+                })
+                .map(Message::Done)
+                .abortable();
+                self.running = Some(handle);
+                self.status = "Working...".to_string();
+                return task;
+            }
+            Message::Abort => {
+                if let Some(handle) = self.running.take() {
+                    handle.abort();
+                    self.status = "Aborted".to_string();
+                }
+            }
+            Message::Done(steps) => {
+                self.running = None;
+                self.status = format!("Done after {} steps", steps);
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Frame::new(&self.status).view(),
+            Row::new(&[
+                Button::new("Start", Message::Start).view(),
+                Button::new("Abort", Message::Abort).view(),
+            ])
+            .view(),
+        ])
+        .view()
+    }
+}
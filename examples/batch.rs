@@ -0,0 +1,54 @@
+use flemish::{view::*, Settings, Subscription};
+use std::time::Duration;
+
+pub fn main() {
+    flemish::application("batch", App::update, App::view)
+        .settings(Settings {
+            size: (300, 100),
+            resizable: true,
+            ..Default::default()
+        })
+        .subscription(App::subscription)
+        .run();
+}
+
+#[derive(Default)]
+struct App {
+    ticks: i32,
+    events: i32,
+    fast: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Tick,
+    Event,
+    Toggle,
+}
+
+impl App {
+    fn subscription(&self) -> Subscription<Message> {
+        // Toggling the speed only restarts the timer, the event listener keeps running
+        let period = if self.fast { 100 } else { 1000 };
+        Subscription::batch(vec![
+            Subscription::every(Duration::from_millis(period)).map(|_| Message::Tick),
+            Subscription::events().map(|_| Message::Event),
+        ])
+    }
+
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Tick => self.ticks += 1,
+            Message::Event => self.events += 1,
+            Message::Toggle => self.fast = !self.fast,
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Frame::new(&format!("ticks: {} events: {}", self.ticks, self.events)).view(),
+            Button::new("Toggle speed", Message::Toggle).view(),
+        ])
+        .view()
+    }
+}
@@ -0,0 +1,63 @@
+use flemish::{view::*, Settings};
+
+pub fn main() {
+    flemish::application("browser", BrowserApp::update, BrowserApp::view)
+        .settings(Settings {
+            size: (400, 300),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct BrowserApp {
+    selected: Vec<i32>,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Select(Vec<i32>),
+    SelectKeys(Vec<String>),
+    Open(i32),
+    Clear,
+}
+
+impl BrowserApp {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Select(lines) => self.selected = lines,
+            Message::SelectKeys(keys) => self.status = format!("Selected {:?}", keys),
+            Message::Open(line) => self.status = format!("Opened line {}", line),
+            Message::Clear => self.selected.clear(),
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            MultiBrowser::new(
+                &[
+                    "@bName\t@bSize",
+                    "Cargo.toml\t1 KB",
+                    "README.md\t4 KB",
+                    "main.rs\t12 KB",
+                ],
+                self.selected.clone(),
+            )
+            .column_widths(&[200, 100])
+            .keys(&["header", "cargo", "readme", "main"])
+            .on_change(Message::Select)
+            .on_select_keys(Message::SelectKeys)
+            .on_double_click(Message::Open)
+            .view(),
+            Row::new(&[
+                Frame::new(&self.status).view(),
+                Button::new("Clear selection", Message::Clear).view(),
+            ])
+            .fixed(30)
+            .view(),
+        ])
+        .view()
+    }
+}
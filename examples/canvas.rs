@@ -0,0 +1,90 @@
+use flemish::{enums::Color, view::*, Settings};
+
+pub fn main() {
+    flemish::application("canvas", Paint::update, Paint::view)
+        .settings(Settings {
+            size: (400, 400),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct Paint {
+    strokes: Vec<Vec<(i32, i32)>>,
+    cursor: (i32, i32),
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Press(i32, i32),
+    Drag(i32, i32),
+    Move(i32, i32),
+    Clear,
+}
+
+impl Paint {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Press(x, y) => self.strokes.push(vec![(x, y)]),
+            Message::Drag(x, y) => {
+                if let Some(stroke) = self.strokes.last_mut() {
+                    stroke.push((x, y));
+                }
+                self.cursor = (x, y);
+            }
+            Message::Move(x, y) => self.cursor = (x, y),
+            Message::Clear => self.strokes.clear(),
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        let mut cmds = vec![
+            DrawCmd::Color(Color::White),
+            DrawCmd::Rect {
+                x: 0,
+                y: 0,
+                w: 2000,
+                h: 2000,
+                filled: true,
+            },
+            DrawCmd::Color(Color::Blue),
+            DrawCmd::LineStyle(LineStyle::Solid, 3),
+        ];
+        for stroke in &self.strokes {
+            for w in stroke.windows(2) {
+                cmds.push(DrawCmd::Line {
+                    x1: w[0].0,
+                    y1: w[0].1,
+                    x2: w[1].0,
+                    y2: w[1].1,
+                });
+            }
+        }
+        cmds.push(DrawCmd::Color(Color::Red));
+        cmds.push(DrawCmd::Arc {
+            x: self.cursor.0 - 4,
+            y: self.cursor.1 - 4,
+            w: 8,
+            h: 8,
+            start: 0.,
+            end: 360.,
+            filled: true,
+        });
+        Column::new(&[
+            Canvas::new(cmds)
+                .on_press(Message::Press)
+                .on_drag(Message::Drag)
+                .on_move(Message::Move)
+                .view(),
+            Row::new(&[
+                Frame::new(&format!("{}, {}", self.cursor.0, self.cursor.1)).view(),
+                Button::new("Clear", Message::Clear).view(),
+            ])
+            .fixed(30)
+            .view(),
+        ])
+        .view()
+    }
+}
@@ -0,0 +1,67 @@
+use flemish::{view::*, Settings, Task};
+use std::time::Duration;
+
+pub fn main() {
+    flemish::application("combinators", App::update, App::view)
+        .settings(Settings {
+            size: (300, 150),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct App {
+    log: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Start,
+    Log(String),
+    Fetched(u64),
+}
+
+fn delayed(ms: u64) -> Task<u64> {
+    Task::perform_async(move || async move {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        ms
+    })
+}
+
+impl App {
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Start => {
+                self.log.clear();
+                // Two fetches in parallel, followed by a dependent step
+                Task::batch(vec![
+                    delayed(300).map(Message::Fetched),
+                    delayed(100).map(Message::Fetched),
+                ])
+                .chain(
+                    delayed(200)
+                        .then(|ms| delayed(ms * 2))
+                        .map(|ms| Message::Log(format!("dependent step took {}ms", ms))),
+                )
+            }
+            Message::Fetched(ms) => {
+                self.log.push(format!("fetched after {}ms", ms));
+                Task::none()
+            }
+            Message::Log(s) => {
+                self.log.push(s);
+                Task::none()
+            }
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Button::new("Start", Message::Start).fixed(30).view(),
+            Frame::new(&self.log.join("\n")).view(),
+        ])
+        .view()
+    }
+}
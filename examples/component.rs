@@ -0,0 +1,81 @@
+use flemish::{view::*, Settings};
+
+pub fn main() {
+    flemish::application("component", App::update, App::view)
+        .settings(Settings {
+            size: (300, 200),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+mod login {
+    use flemish::view::*;
+
+    #[derive(Default)]
+    pub struct LoginForm {
+        pub user: String,
+        pub password: String,
+    }
+
+    #[derive(Debug, Clone)]
+    pub enum LoginMsg {
+        User(String),
+        Password(String),
+        Submit,
+    }
+
+    impl LoginForm {
+        pub fn update(&mut self, msg: LoginMsg) {
+            match msg {
+                LoginMsg::User(s) => self.user = s,
+                LoginMsg::Password(s) => self.password = s,
+                LoginMsg::Submit => (),
+            }
+        }
+
+        pub fn view(&self) -> View<LoginMsg> {
+            Column::new(&[
+                Input::new(&self.user).on_input(LoginMsg::User).view(),
+                SecretInput::new(&self.password)
+                    .on_input(LoginMsg::Password)
+                    .view(),
+                Button::new("Login", LoginMsg::Submit).view(),
+            ])
+            .view()
+        }
+    }
+}
+
+use login::{LoginForm, LoginMsg};
+
+#[derive(Default)]
+struct App {
+    form: LoginForm,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Login(LoginMsg),
+}
+
+impl App {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Login(LoginMsg::Submit) => {
+                self.status = format!("Welcome {}", self.form.user);
+            }
+            Message::Login(msg) => self.form.update(msg),
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            self.form.view().map(Message::Login),
+            Frame::new(&self.status).view(),
+        ])
+        .view()
+    }
+}
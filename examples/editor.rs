@@ -1,5 +1,5 @@
 use flemish::{
-    dialog,
+    dialog::FileOptions,
     enums::{Align, Shortcut},
     theme::color_themes,
     view::*,
@@ -32,6 +32,9 @@ struct Editor {
     content: String,
     saved: bool,
     load_path: Option<PathBuf>,
+    search: String,
+    replacement: String,
+    cursor: (i32, i32),
 }
 
 impl Default for Editor {
@@ -41,6 +44,9 @@ impl Default for Editor {
             content: String::new(),
             saved: true,
             load_path: None,
+            search: String::new(),
+            replacement: String::new(),
+            cursor: (1, 1),
         }
     }
 }
@@ -48,10 +54,17 @@ impl Default for Editor {
 #[derive(Clone, Debug)]
 enum Message {
     Changed(String),
+    FileNew,
     FileOpen,
+    Opened(Option<PathBuf>),
     FileSave,
     FileSaveAs,
+    SavedAs(Option<PathBuf>),
     Quit,
+    ConfirmedQuit(bool),
+    Search(String),
+    Replacement(String),
+    Cursor(i32, i32),
     TextEditorCommand(TextEditorCommand<Message>),
 }
 
@@ -63,7 +76,7 @@ impl Editor {
             path,
             content,
             saved,
-            load_path: None,
+            ..Default::default()
         })
     }
     fn update(&mut self, message: Message) -> Result<Task<Message>, Box<dyn std::error::Error>> {
@@ -71,48 +84,55 @@ impl Editor {
             Message::Changed(s) => {
                 self.saved = false;
                 self.content = s;
+                return Ok(Task::done(Message::TextEditorCommand(
+                    TextEditorCommand::CursorPosition(Message::Cursor),
+                )));
             }
+            Message::FileNew => {
+                // The editor is controlled, so clearing the content clears the buffer
+                self.content.clear();
+                self.path = PathBuf::new();
+                self.saved = true;
+                self.load_path = None;
+            }
+            Message::Search(s) => self.search = s,
+            Message::Replacement(s) => self.replacement = s,
+            Message::Cursor(line, col) => self.cursor = (line, col),
             Message::FileOpen => {
-                let mut nfc =
-                    dialog::NativeFileChooser::new(dialog::NativeFileChooserType::BrowseFile);
-                nfc.show();
-                let p = nfc.filename();
-                if !p.as_os_str().is_empty() {
-                    self.content = std::fs::read_to_string(&p)?;
-                    self.path = p.clone();
-                    self.saved = true;
-                    self.load_path = Some(p);
-                }
+                return Ok(Task::open_file(FileOptions::new().title("Open")).map(Message::Opened));
+            }
+            Message::Opened(Some(p)) => {
+                self.content = std::fs::read_to_string(&p)?;
+                self.path = p.clone();
+                self.saved = true;
+                self.load_path = Some(p);
             }
             Message::FileSave => {
+                if self.path.as_os_str().is_empty() {
+                    return Ok(Task::save_file(FileOptions::new()).map(Message::SavedAs));
+                }
                 std::fs::write(&self.path, &self.content)?;
                 self.saved = true;
             }
             Message::FileSaveAs => {
-                let mut nfc =
-                    dialog::NativeFileChooser::new(dialog::NativeFileChooserType::BrowseSaveFile);
-                nfc.show();
-                let p = nfc.filename();
-                if !p.as_os_str().is_empty() {
-                    std::fs::write(&p, &self.content)?;
-                    self.path = p;
-                    self.saved = true;
-                }
+                let opts = FileOptions::new().title("Save as");
+                return Ok(Task::save_file(opts).map(Message::SavedAs));
+            }
+            Message::SavedAs(Some(p)) => {
+                std::fs::write(&p, &self.content)?;
+                self.path = p;
+                self.saved = true;
             }
             Message::Quit => {
                 if self.saved {
                     return Ok(Task::exit());
-                } else if let Some(choice) = dialog::choice_default(
-                    "You have unsaved changes, are you sure you want to exit?",
-                    "Yes",
-                    "No",
-                    "",
-                ) {
-                    if choice == 0 {
-                        return Ok(Task::exit());
-                    }
                 }
+                return Ok(Task::confirm(
+                    "You have unsaved changes, are you sure you want to exit?",
+                )
+                .map(Message::ConfirmedQuit));
             }
+            Message::ConfirmedQuit(true) => return Ok(Task::exit()),
             _ => {}
         }
         Ok(Task::none())
@@ -121,6 +141,12 @@ impl Editor {
     fn view(&self) -> View<Message> {
         Column::new(&[
             MenuBar::new(&[
+                MenuItem::new(
+                    "&File/&New\t",
+                    Shortcut::Ctrl | 'n',
+                    MenuFlag::Normal,
+                    Message::FileNew,
+                ),
                 MenuItem::new(
                     "&File/&Open\t",
                     Shortcut::Ctrl | 'o',
@@ -145,6 +171,18 @@ impl Editor {
                     MenuFlag::Normal,
                     Message::Quit,
                 ),
+                MenuItem::new(
+                    "&Edit/&Undo\t",
+                    Shortcut::Ctrl | 'z',
+                    MenuFlag::Normal,
+                    Message::TextEditorCommand(TextEditorCommand::Undo),
+                ),
+                MenuItem::new(
+                    "&Edit/&Redo\t",
+                    Shortcut::Ctrl | Shortcut::Shift | 'z',
+                    MenuFlag::MenuDivider,
+                    Message::TextEditorCommand(TextEditorCommand::Redo),
+                ),
                 MenuItem::new(
                     "&Edit/Cu&t\t",
                     Shortcut::Ctrl | 'x',
@@ -163,6 +201,18 @@ impl Editor {
                     MenuFlag::Normal,
                     Message::TextEditorCommand(TextEditorCommand::Paste),
                 ),
+                MenuItem::new(
+                    "&Edit/Select &all\t",
+                    Shortcut::Ctrl | 'a',
+                    MenuFlag::Normal,
+                    Message::TextEditorCommand(TextEditorCommand::SelectAll),
+                ),
+                MenuItem::new(
+                    "&Edit/&Go to start\t",
+                    Shortcut::Ctrl | 'g',
+                    MenuFlag::Normal,
+                    Message::TextEditorCommand(TextEditorCommand::GotoLine(1)),
+                ),
             ])
             .fixed(30)
             .view(),
@@ -179,10 +229,43 @@ impl Editor {
                 _ => None,
             })
             .view(),
-            Frame::new(if self.saved { "" } else { "Not saved" })
-                .align(Align::Left | Align::Inside)
-                .fixed(20)
+            Row::new(&[
+                Input::new(&self.search).on_input(Message::Search).view(),
+                Button::new(
+                    "Find",
+                    Message::TextEditorCommand(TextEditorCommand::Find {
+                        pattern: self.search.clone(),
+                        backwards: false,
+                        case: false,
+                    }),
+                )
+                .fixed(60)
+                .view(),
+                Input::new(&self.replacement)
+                    .on_input(Message::Replacement)
+                    .view(),
+                Button::new(
+                    "Replace all",
+                    Message::TextEditorCommand(TextEditorCommand::ReplaceAll {
+                        pattern: self.search.clone(),
+                        replacement: self.replacement.clone(),
+                        case: true,
+                    }),
+                )
+                .fixed(90)
                 .view(),
+            ])
+            .fixed(30)
+            .view(),
+            Frame::new(&format!(
+                "Ln {}, Col {}{}",
+                self.cursor.0,
+                self.cursor.1,
+                if self.saved { "" } else { "  Not saved" }
+            ))
+            .align(Align::Left | Align::Inside)
+            .fixed(20)
+            .view(),
         ])
         .view()
     }
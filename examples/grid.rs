@@ -0,0 +1,77 @@
+use flemish::{
+    enums::{Align, GridAlign},
+    view::*,
+    Settings,
+};
+
+pub fn main() {
+    flemish::application("grid", Calc::update, Calc::view)
+        .settings(Settings {
+            size: (260, 300),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct Calc {
+    display: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Press(char),
+    Clear,
+}
+
+impl Calc {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Press(c) => self.display.push(c),
+            Message::Clear => self.display.clear(),
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        let mut children = vec![
+            Frame::new(&self.display)
+                .align(Align::Right | Align::Inside)
+                .cell(0, 0)
+                .span(1, 3)
+                .view(),
+            Button::new("C", Message::Clear)
+                .cell(0, 3)
+                .grid_align(GridAlign::CENTER)
+                .w(30)
+                .h(30)
+                .view(),
+        ];
+        for (i, c) in "789456123".chars().enumerate() {
+            children.push(
+                Button::new(&c.to_string(), Message::Press(c))
+                    .cell(1 + i as i32 / 3, i as i32 % 3)
+                    .view(),
+            );
+        }
+        children.push(
+            Button::new("0", Message::Press('0'))
+                .cell(4, 0)
+                .span(1, 3)
+                .view(),
+        );
+        children.push(
+            Button::new("+", Message::Press('+'))
+                .cell(1, 3)
+                .span(4, 1)
+                .view(),
+        );
+        Grid::new(&children)
+            .layout(5, 4)
+            .gap(5, 5)
+            .row_weights(&[2, 1, 1, 1, 1])
+            .row_heights(&[40])
+            .col_widths(&[0, 0, 0, 50])
+            .view()
+    }
+}
@@ -0,0 +1,82 @@
+use flemish::{
+    enums::{Color, Font},
+    view::*,
+    Settings,
+};
+use std::ops::Range;
+
+pub fn main() {
+    flemish::application("highlight", Log::update, Log::view)
+        .settings(Settings {
+            size: (500, 300),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+const STYLES: [StyleTableEntry; 3] = [
+    StyleTableEntry {
+        color: Color::Foreground,
+        font: Font::Courier,
+        size: 14,
+    },
+    StyleTableEntry {
+        color: Color::Red,
+        font: Font::CourierBold,
+        size: 14,
+    },
+    StyleTableEntry {
+        color: Color::DarkYellow,
+        font: Font::Courier,
+        size: 14,
+    },
+];
+
+/// Styles the level of each log line
+struct LevelTokenizer;
+
+impl Tokenizer for LevelTokenizer {
+    fn tokens(&self, line: &str) -> Vec<(Range<usize>, char)> {
+        let mut tokens = vec![];
+        for (level, style) in [("ERROR", 'B'), ("WARN", 'C')] {
+            if let Some(start) = line.find(level) {
+                tokens.push((start..start + level.len(), style));
+            }
+        }
+        tokens
+    }
+}
+
+const SAMPLE: &str = "[INFO] server started
+[WARN] config file not found, using defaults
+[INFO] listening on port 8080
+[ERROR] connection refused
+";
+
+#[derive(Default)]
+struct Log {
+    content: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Changed(String),
+}
+
+impl Log {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Changed(s) => self.content = s,
+        }
+    }
+
+    // Edited lines are restyled as they are typed
+    fn view(&self) -> View<Message> {
+        Column::new(&[TextEditor::new(SAMPLE)
+            .highlight_with(&STYLES, LevelTokenizer)
+            .on_input(Message::Changed)
+            .view()])
+        .view()
+    }
+}
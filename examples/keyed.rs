@@ -0,0 +1,72 @@
+use flemish::{view::*, Settings};
+
+pub fn main() {
+    flemish::application("keyed", Keyed::update, Keyed::view)
+        .settings(Settings {
+            size: (300, 400),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct Keyed {
+    next: usize,
+    items: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Prepend,
+    RemoveFirst,
+    Edit(usize, String),
+}
+
+impl Keyed {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Prepend => {
+                self.items
+                    .insert(0, (self.next, format!("item {}", self.next)));
+                self.next += 1;
+            }
+            Message::RemoveFirst => {
+                if !self.items.is_empty() {
+                    self.items.remove(0);
+                }
+            }
+            Message::Edit(id, s) => {
+                if let Some(item) = self.items.iter_mut().find(|(i, _)| *i == id) {
+                    item.1 = s;
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        // Keys let the rows keep their widgets (and focus) when items are inserted at the top
+        let rows: Vec<View<Message>> = self
+            .items
+            .iter()
+            .map(|(id, s)| {
+                let id = *id;
+                Input::new(s)
+                    .on_input(move |s| Message::Edit(id, s))
+                    .key(&id.to_string())
+                    .fixed(30)
+                    .view()
+            })
+            .collect();
+        Column::new(&[
+            Row::new(&[
+                Button::new("Prepend", Message::Prepend).view(),
+                Button::new("Remove first", Message::RemoveFirst).view(),
+            ])
+            .fixed(30)
+            .view(),
+            Column::new(&rows).view(),
+        ])
+        .view()
+    }
+}
@@ -0,0 +1,86 @@
+use flemish::{enums::FrameType, view::*, Settings};
+
+pub fn main() {
+    flemish::application("layout", Layout::update, Layout::view)
+        .settings(Settings {
+            size: (400, 300),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct Layout {
+    sidebar: bool,
+    centered: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Sidebar(bool),
+    Centered(bool),
+}
+
+impl Layout {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Sidebar(flag) => self.sidebar = flag,
+            Message::Centered(flag) => self.centered = flag,
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        let align = if self.centered {
+            FlexAlign::Center
+        } else {
+            FlexAlign::Stretch
+        };
+        Column::new(&[
+            Row::new(&[
+                CheckButton::new("Sidebar", self.sidebar)
+                    .on_change(Message::Sidebar)
+                    .view(),
+                CheckButton::new("Centered", self.centered)
+                    .on_change(Message::Centered)
+                    .view(),
+                Space::new().view(),
+            ])
+            .fixed(30)
+            .view(),
+            Row::new(&[
+                Frame::new("Sidebar")
+                    .boxtype(FrameType::DownBox)
+                    .min_size(80)
+                    .max_size(120)
+                    .visible(self.sidebar)
+                    .view(),
+                Column::new(&[
+                    Button::new("Grows twice as much", Message::Centered(!self.centered))
+                        .flex(2)
+                        .w(200)
+                        .view(),
+                    Button::new("At most 40 high", Message::Sidebar(!self.sidebar))
+                        .max_size(40)
+                        .w(160)
+                        .view(),
+                    Space::new().fixed(10).view(),
+                    Frame::new("Right aligned")
+                        .boxtype(FrameType::DownBox)
+                        .align_self(FlexAlign::End)
+                        .w(120)
+                        .view(),
+                ])
+                .align_items(align)
+                .padding(5)
+                .flex(3)
+                .view(),
+            ])
+            .padding(10)
+            .view(),
+        ])
+        .margins(10, 10, 10, 10)
+        .padding(10)
+        .view()
+    }
+}
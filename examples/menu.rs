@@ -1,58 +1,115 @@
 use flemish::{enums::Shortcut, view::*, Settings};
 
 pub fn main() {
-    flemish::application("menu app", MenuApp::update, MenuApp::view)
+    flemish::application("menu", Notes::update, Notes::view)
         .settings(Settings {
-            size: (300, 300),
+            size: (400, 300),
             resizable: true,
-            ignore_esc_close: true,
             ..Default::default()
         })
         .run();
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Size {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
 #[derive(Default)]
-struct MenuApp {
-    value: i32,
+struct Notes {
+    notes: Vec<String>,
+    bold: bool,
+    size: Size,
+    status: String,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum Message {
-    Increment,
-    Decrement,
+    Add,
+    Clear,
+    Bold,
+    Size(Size),
+    Copy(usize),
+    Remove(usize),
 }
 
-impl MenuApp {
+impl Notes {
     fn update(&mut self, message: Message) {
         match message {
-            Message::Increment => {
-                self.value += 1;
-            }
-            Message::Decrement => {
-                self.value -= 1;
+            Message::Add => self.notes.push(format!("Note {}", self.notes.len() + 1)),
+            Message::Clear => self.notes.clear(),
+            Message::Bold => self.bold = !self.bold,
+            Message::Size(s) => self.size = s,
+            Message::Copy(n) => self.status = format!("Copied note {}", n + 1),
+            Message::Remove(n) => {
+                self.notes.remove(n);
             }
         }
     }
 
     fn view(&self) -> View<Message> {
+        let menu = Menu::new()
+            .submenu(
+                "&Notes",
+                Menu::new()
+                    .item(MenuItem::action("&Add", Message::Add).shortcut(Shortcut::Ctrl | 'n'))
+                    .item(
+                        MenuItem::action("&Clear", Message::Clear).enabled(!self.notes.is_empty()),
+                    ),
+            )
+            .submenu(
+                "&View",
+                Menu::new()
+                    .check("&Bold", self.bold, Message::Bold)
+                    .divider()
+                    .radio(
+                        "Small",
+                        self.size == Size::Small,
+                        Message::Size(Size::Small),
+                    )
+                    .radio(
+                        "Medium",
+                        self.size == Size::Medium,
+                        Message::Size(Size::Medium),
+                    )
+                    .radio(
+                        "Large",
+                        self.size == Size::Large,
+                        Message::Size(Size::Large),
+                    ),
+            );
+        let label_size = match self.size {
+            Size::Small => 12,
+            Size::Medium => 14,
+            Size::Large => 18,
+        };
+        let font = if self.bold {
+            flemish::enums::Font::HelveticaBold
+        } else {
+            flemish::enums::Font::Helvetica
+        };
+        let notes: Vec<View<Message>> = self
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                ContextMenu::new(
+                    Frame::new(n).label_size(label_size).label_font(font).view(),
+                    Menu::new()
+                        .action("Copy", Message::Copy(i))
+                        .action("Remove", Message::Remove(i)),
+                )
+                .fixed(30)
+                .view()
+            })
+            .collect();
         Column::new(&[
-            MenuBar::new(&[
-                MenuItem::new(
-                    "Command/Increment",
-                    Shortcut::None,
-                    MenuFlag::Normal,
-                    Message::Increment,
-                ),
-                MenuItem::new(
-                    "Command/Decrement",
-                    Shortcut::None,
-                    MenuFlag::Normal,
-                    Message::Decrement,
-                ),
-            ])
-            .fixed(30)
-            .view(),
-            Frame::new(&self.value.to_string()).view(),
+            MenuBar::from_menu(menu).fixed(30).view(),
+            Column::new(&notes).view(),
+            Frame::new(&self.status).fixed(30).view(),
         ])
         .view()
     }
@@ -0,0 +1,78 @@
+use flemish::{view::*, Settings, Subscription};
+use std::collections::VecDeque;
+
+const WINDOW: usize = 200;
+
+pub fn main() {
+    flemish::application("plot", State::update, State::view)
+        .settings(Settings {
+            size: (600, 400),
+            resizable: true,
+            ..Default::default()
+        })
+        .subscription(State::subscription)
+        .run();
+}
+
+#[derive(Default)]
+struct State {
+    t: f64,
+    sin: VecDeque<(f64, f64)>,
+    cos: VecDeque<(f64, f64)>,
+    paused: bool,
+    hide_grid: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Tick,
+    Pause,
+    Grid,
+}
+
+impl State {
+    fn subscription(&self) -> Subscription<Message> {
+        if self.paused {
+            Subscription::none()
+        } else {
+            Subscription::every(std::time::Duration::from_millis(30)).map(|_| Message::Tick)
+        }
+    }
+
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Tick => {
+                self.t += 0.05;
+                self.sin.push_back((self.t, self.t.sin()));
+                self.cos.push_back((self.t, 0.5 * (2. * self.t).cos()));
+                if self.sin.len() > WINDOW {
+                    self.sin.pop_front();
+                    self.cos.pop_front();
+                }
+            }
+            Message::Pause => self.paused = !self.paused,
+            Message::Grid => self.hide_grid = !self.hide_grid,
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        let sin: Vec<_> = self.sin.iter().copied().collect();
+        let cos: Vec<_> = self.cos.iter().copied().collect();
+        Column::new(&[
+            Plot::new(&[
+                Series::new("sin(t)", &sin),
+                Series::new("cos(2t) / 2", &cos).kind(SeriesKind::Scatter),
+            ])
+            .y_range(-1.2, 1.2)
+            .grid(!self.hide_grid)
+            .view(),
+            Row::new(&[
+                Button::new(if self.paused { "Resume" } else { "Pause" }, Message::Pause).view(),
+                Button::new("Toggle grid", Message::Grid).view(),
+            ])
+            .fixed(30)
+            .view(),
+        ])
+        .view()
+    }
+}
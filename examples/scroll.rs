@@ -0,0 +1,73 @@
+use flemish::{
+    enums::{FrameType, ScrollType},
+    view::*,
+    Settings, Task,
+};
+
+const ITEMS: i32 = 100;
+
+pub fn main() {
+    flemish::application("scroll", List::update, List::view)
+        .settings(Settings {
+            size: (300, 400),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct List {
+    position: (i32, i32),
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Scrolled(i32, i32),
+    Top,
+    Find(i32),
+}
+
+impl List {
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::Scrolled(x, y) => self.position = (x, y),
+            Message::Top => self.position = (0, 0),
+            Message::Find(n) => return Task::scroll_to_node(&format!("item{n}")),
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> View<Message> {
+        let items: Vec<View<Message>> = (0..ITEMS)
+            .map(|i| {
+                Frame::new(&format!("Item {}", i + 1))
+                    .boxtype(FrameType::DownBox)
+                    .key(&format!("item{i}"))
+                    .view()
+            })
+            .collect();
+        Column::new(&[
+            Row::new(&[
+                Button::new("Top", Message::Top).view(),
+                Button::new("Item 50", Message::Find(49)).view(),
+                Button::new("Last", Message::Find(ITEMS - 1)).view(),
+            ])
+            .padding(5)
+            .fixed(30)
+            .view(),
+            Scroll::new(&[Column::new(&items).padding(2).w(260).h(ITEMS * 32).view()])
+                .scroll_to(self.position.0, self.position.1)
+                .scrollbars(ScrollType::Vertical)
+                .scrollbar_size(12)
+                .on_scroll(Message::Scrolled)
+                .view(),
+            Frame::new(&format!("Scrolled to {}", self.position.1))
+                .fixed(30)
+                .view(),
+        ])
+        .margins(10, 10, 10, 10)
+        .padding(10)
+        .view()
+    }
+}
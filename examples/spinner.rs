@@ -0,0 +1,76 @@
+use flemish::{view::*, Settings};
+
+const SIZES: [&str; 5] = ["Small", "Medium", "Large", "XL", "XXL"];
+
+pub fn main() {
+    flemish::application("spinner", Order::update, Order::view)
+        .settings(Settings {
+            size: (300, 160),
+            resizable: true,
+            ..Default::default()
+        })
+        .run_with(Order::new);
+}
+
+struct Order {
+    size: String,
+    quantity: f64,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Size(String),
+    Quantity(f64),
+    Submit,
+    Reset,
+}
+
+impl Order {
+    fn new() -> Self {
+        Self {
+            size: "Medium".to_string(),
+            quantity: 1.,
+            status: String::new(),
+        }
+    }
+
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Size(s) => self.size = s,
+            Message::Quantity(q) => self.quantity = q,
+            Message::Submit => {
+                self.status = format!("Ordered {} x {}", self.quantity, self.size);
+            }
+            Message::Reset => *self = Self::new(),
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            InputChoice::new("Size")
+                .items(&SIZES)
+                .value(&self.size)
+                .on_change(Message::Size)
+                .on_submit(|_| Message::Submit)
+                .fixed(30)
+                .view(),
+            Spinner::new("Quantity")
+                .value(self.quantity)
+                .range(1., 10.)
+                .on_change(Message::Quantity)
+                .on_submit(|_| Message::Submit)
+                .fixed(30)
+                .view(),
+            Row::new(&[
+                Button::new("Order", Message::Submit).view(),
+                Button::new("Reset", Message::Reset).view(),
+            ])
+            .fixed(30)
+            .view(),
+            Frame::new(&self.status).view(),
+        ])
+        .margins(70, 10, 10, 10)
+        .view()
+    }
+}
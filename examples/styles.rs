@@ -0,0 +1,83 @@
+use flemish::{
+    enums::{Color, Font, FrameType},
+    view::*,
+    vnode::VNodeType,
+    Settings, Style, StyleSheet,
+};
+
+pub fn main() {
+    flemish::application("styles", Form::update, Form::view)
+        .settings(Settings {
+            size: (300, 200),
+            resizable: true,
+            ..Default::default()
+        })
+        .stylesheet(
+            StyleSheet::new()
+                .typ(
+                    VNodeType::Button,
+                    Style::new().boxtype(FrameType::RFlatBox).label_size(14),
+                )
+                .typ(VNodeType::Input, Style::new().text_font(Font::Courier))
+                .class(
+                    "primary",
+                    Style::new()
+                        .color(Color::from_hex(0x1e88e5))
+                        .label_color(Color::White),
+                )
+                .class(
+                    "danger",
+                    Style::new()
+                        .color(Color::from_hex(0xe53935))
+                        .label_color(Color::White),
+                ),
+        )
+        .run();
+}
+
+#[derive(Default)]
+struct Form {
+    name: String,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Name(String),
+    Save,
+    Delete,
+}
+
+impl Form {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Name(s) => self.name = s,
+            Message::Save => self.status = format!("Saved {}", self.name),
+            Message::Delete => {
+                self.name.clear();
+                self.status = "Deleted".to_string();
+            }
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Input::new(&self.name).on_input(Message::Name).view(),
+            Row::new(&[
+                Button::new("Save", Message::Save).class("primary").view(),
+                // Explicit props win over the class
+                Button::new("Delete", Message::Delete)
+                    .class("danger")
+                    .label_font(Font::HelveticaBold)
+                    .view(),
+            ])
+            .padding(10)
+            .fixed(30)
+            .view(),
+            Frame::new(&self.status).view(),
+        ])
+        .margins(10, 10, 10, 10)
+        .padding(10)
+        .view()
+    }
+}
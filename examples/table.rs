@@ -3,7 +3,7 @@ use flemish::{view::*, Settings};
 pub fn main() {
     flemish::application("table-app", TableApp::update, TableApp::view)
         .settings(Settings {
-            size: (300, 100),
+            size: (400, 300),
             resizable: true,
             color_map: flemish::theme::color_themes::GRAY_THEME,
             ..Default::default()
@@ -11,18 +11,93 @@ pub fn main() {
         .run();
 }
 
-#[derive(Default)]
-struct TableApp {}
+struct Person {
+    name: String,
+    age: u32,
+}
+
+struct TableApp {
+    people: Vec<Person>,
+    sort: (i32, bool),
+    status: String,
+}
+
+impl Default for TableApp {
+    fn default() -> Self {
+        let people = [("Alice", 32), ("Bob", 27), ("Carol", 45)]
+            .iter()
+            .map(|(name, age)| Person {
+                name: name.to_string(),
+                age: *age,
+            })
+            .collect();
+        Self {
+            people,
+            sort: (0, true),
+            status: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Sort(i32),
+    Select(Vec<i32>),
+    Edit(i32, i32, String),
+    Resize(i32, i32),
+}
 
 impl TableApp {
-    fn update(&mut self, _message: i32) {}
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Sort(col) => {
+                self.sort = if self.sort.0 == col {
+                    (col, !self.sort.1)
+                } else {
+                    (col, true)
+                };
+                let (col, ascending) = self.sort;
+                self.people.sort_by(|a, b| {
+                    let ord = if col == 0 {
+                        a.name.cmp(&b.name)
+                    } else {
+                        a.age.cmp(&b.age)
+                    };
+                    if ascending {
+                        ord
+                    } else {
+                        ord.reverse()
+                    }
+                });
+            }
+            Message::Select(rows) => self.status = format!("Selected {:?}", rows),
+            Message::Edit(row, col, value) => {
+                if let Some(p) = self.people.get_mut(row as usize) {
+                    if col == 0 {
+                        p.name = value;
+                    } else if let Ok(age) = value.parse() {
+                        p.age = age;
+                    }
+                }
+            }
+            Message::Resize(col, w) => self.status = format!("Column {} is {}px wide", col, w),
+        }
+    }
 
-    fn view(&self) -> View<i32> {
-        let v: &[&[&str]] = &[
-            &["String1", "String2"],
-            &["String3", "String4"],
-            &["String5", "String6"],
-        ];
-        Column::new(&[Table::new(&["Col1", "Col2"], v).view()]).view()
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Table::from_rows(&["Name", "Age"], &self.people, |p| {
+                vec![p.name.clone(), p.age.to_string()]
+            })
+            .sort_indicator(self.sort.0, self.sort.1)
+            .editable(true)
+            .on_header_click(Message::Sort)
+            .on_select(Message::Select)
+            .on_edit(Message::Edit)
+            .on_col_resize(Message::Resize)
+            .view(),
+            Frame::new(&self.status).fixed(30).view(),
+        ])
+        .view()
     }
 }
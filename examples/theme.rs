@@ -0,0 +1,67 @@
+use flemish::{
+    theme::{color_themes, Theme},
+    view::*,
+    Scheme, Settings,
+};
+
+pub fn main() {
+    flemish::application("theme", State::update, State::view)
+        .settings(Settings {
+            size: (300, 160),
+            resizable: true,
+            ..Default::default()
+        })
+        .theme(State::theme)
+        .run();
+}
+
+#[derive(Default)]
+struct State {
+    light: bool,
+    plastic: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    Light(bool),
+    Plastic(bool),
+}
+
+impl State {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Light(flag) => self.light = flag,
+            Message::Plastic(flag) => self.plastic = flag,
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        Theme {
+            color_map: if self.light {
+                &color_themes::fleet::SOLARIZED_LIGHT
+            } else {
+                &color_themes::fleet::GRUVBOX_DARK
+            },
+            scheme: if self.plastic {
+                Scheme::Plastic
+            } else {
+                Scheme::Gtk
+            },
+            ..Default::default()
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            CheckButton::new("Light theme", self.light)
+                .on_change(Message::Light)
+                .view(),
+            CheckButton::new("Plastic scheme", self.plastic)
+                .on_change(Message::Plastic)
+                .view(),
+            Frame::new("The quick brown fox").view(),
+        ])
+        .margins(10, 10, 10, 10)
+        .view()
+    }
+}
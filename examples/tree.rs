@@ -0,0 +1,69 @@
+use flemish::{view::*, Settings};
+
+pub fn main() {
+    flemish::application("tree", TreeApp::update, TreeApp::view)
+        .settings(Settings {
+            size: (300, 400),
+            resizable: true,
+            ..Default::default()
+        })
+        .run();
+}
+
+#[derive(Default)]
+struct TreeApp {
+    done: Vec<String>,
+    status: String,
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Select(TreeNode),
+    Check(TreeNode, bool),
+}
+
+impl TreeApp {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Select(node) => {
+                self.status = format!("{} ({})", node.path, node.key);
+            }
+            Message::Check(node, checked) => {
+                self.done.retain(|k| *k != node.key);
+                if checked {
+                    self.done.push(node.key);
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        let task = |key: &str, label: &str| {
+            TreeItem::new(label)
+                .key(key)
+                .checked(self.done.iter().any(|k| k == key))
+                .tooltip(&format!("Task {}", key))
+        };
+        // Both projects have a "src" folder, told apart by their keys
+        let items = [
+            TreeItem::new("app")
+                .key("app")
+                .children(&[TreeItem::new("src")
+                    .key("app-src")
+                    .children(&[task("1", "main.rs"), task("2", "lib.rs")])]),
+            TreeItem::new("server")
+                .key("server")
+                .children(&[TreeItem::new("src")
+                    .key("server-src")
+                    .children(&[task("3", "main.rs")])]),
+        ];
+        Column::new(&[
+            Tree::new(&items)
+                .on_select(Message::Select)
+                .on_check(Message::Check)
+                .view(),
+            Frame::new(&self.status).fixed(30).view(),
+        ])
+        .view()
+    }
+}
@@ -0,0 +1,59 @@
+use flemish::{view::*, Settings, WindowId};
+
+pub fn main() {
+    flemish::application("windows", App::update, App::view)
+        .settings(Settings {
+            size: (300, 100),
+            resizable: true,
+            ..Default::default()
+        })
+        .windows(App::windows)
+        .run();
+}
+
+#[derive(Default)]
+struct App {
+    show_about: bool,
+    count: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Message {
+    ToggleAbout,
+    CloseAbout,
+    Increment,
+}
+
+impl App {
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::ToggleAbout => self.show_about = !self.show_about,
+            Message::CloseAbout => self.show_about = false,
+            Message::Increment => self.count += 1,
+        }
+    }
+
+    fn view(&self) -> View<Message> {
+        Column::new(&[
+            Button::new("Toggle about window", Message::ToggleAbout).view(),
+            Button::new("+", Message::Increment).view(),
+        ])
+        .view()
+    }
+
+    fn windows(&self) -> Vec<(WindowId, View<Message>)> {
+        let mut windows = vec![];
+        if self.show_about {
+            windows.push((
+                WindowId::new("about"),
+                Window::new(&[Frame::new(&format!("Clicked {} times", self.count)).view()])
+                    .label("About")
+                    .w(200)
+                    .h(100)
+                    .on_close(Message::CloseAbout)
+                    .view(),
+            ));
+        }
+        windows
+    }
+}
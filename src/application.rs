@@ -1,14 +1,21 @@
 use fltk::{enums::Event, prelude::*, *};
 #[cfg(feature = "fltk-accesskit")]
 use fltk_accesskit::builder;
+use std::collections::HashMap;
 use std::sync::{atomic::Ordering, Arc};
 
-use crate::id;
+use crate::id::WindowId;
 use crate::settings::Settings;
+use crate::style::{Style, StyleSheet};
 use crate::subscription::*;
 use crate::task::Task;
+use crate::theme::Theme;
 use crate::vdom;
-use crate::view::View;
+use crate::view::{HasProps, VNode, View};
+use crate::vnode::VNodeType;
+
+/// The size of the secondary windows whose view isn't a `Window`.
+const DEFAULT_WINDOW_SIZE: (i32, i32) = (400, 300);
 
 pub struct Application<T, Message: Send + Sync + 'static, U: Into<Task<Message>>> {
     title: String,
@@ -16,6 +23,10 @@ pub struct Application<T, Message: Send + Sync + 'static, U: Into<Task<Message>>
     view_fn: fn(&T) -> View<Message>,
     settings: Option<Settings<Message>>,
     subscription: Option<fn(&T) -> Subscription<Message>>,
+    #[allow(clippy::type_complexity)]
+    windows: Option<fn(&T) -> Vec<(WindowId, View<Message>)>>,
+    stylesheet: Option<StyleSheet>,
+    theme: Option<fn(&T) -> Theme>,
 }
 
 impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> Application<T, Message, U> {
@@ -30,6 +41,9 @@ impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> Applicat
             view_fn,
             settings: None,
             subscription: None,
+            windows: None,
+            stylesheet: None,
+            theme: None,
         }
     }
     pub fn settings(mut self, settings: Settings<Message>) -> Self {
@@ -41,56 +55,98 @@ impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> Applicat
         self
     }
 
+    /// Secondary windows, opened when their id appears and closed when it goes away.
+    /// A view which isn't a `Window` is shown in a resizable window of
+    /// 400x300; return a `Window` to set the size, title or close message.
+    #[allow(clippy::type_complexity)]
+    pub fn windows(mut self, windows_fn: fn(&T) -> Vec<(WindowId, View<Message>)>) -> Self {
+        self.windows = Some(windows_fn);
+        self
+    }
+
+    /// Styles the views of all the windows, see `StyleSheet`.
+    pub fn stylesheet(mut self, sheet: StyleSheet) -> Self {
+        self.stylesheet = Some(sheet);
+        self
+    }
+
+    /// Derives the theme from the state, applying it again whenever it changes.
+    /// It takes precedence over the theme of the settings.
+    pub fn theme(mut self, theme_fn: fn(&T) -> Theme) -> Self {
+        self.theme = Some(theme_fn);
+        self
+    }
+
     fn update(&mut self, t: &mut T, message: Message) -> Task<Message> {
         (self.update_fn)(t, message).into()
     }
 
     fn view_(&self, t: &T) -> View<Message> {
         let mut v = (self.view_fn)(t);
-        id::reset_id();
+        if let Some(sheet) = &self.stylesheet {
+            v.apply_styles(sheet, &Style::new());
+        }
+        // ids are never reused: nodes mounted during a patch must not collide
+        // with the ids of widgets that are still alive
         v.assign_ids_topdown();
         v
     }
+
+    // Opens, patches and closes the secondary windows to match the returned list
+    fn update_windows(&self, t: &T, open: &mut HashMap<WindowId, vdom::VirtualDom<Message>>) {
+        let Some(windows_fn) = self.windows else {
+            return;
+        };
+        let mut windows = windows_fn(t);
+        open.retain(|id, dom| {
+            if windows.iter().any(|(wid, _)| wid == id) {
+                true
+            } else {
+                if let Some(root) = dom.root.borrow_mut().as_mut() {
+                    root.unmount(dom);
+                }
+                false
+            }
+        });
+        for (id, mut v) in windows.drain(..) {
+            if *v.typ() != VNodeType::Window {
+                let (w, h) = DEFAULT_WINDOW_SIZE;
+                v = crate::view::Window::new(&[v])
+                    .resizable(true)
+                    .w(w)
+                    .h(h)
+                    .view();
+            }
+            if let Some(sheet) = &self.stylesheet {
+                v.apply_styles(sheet, &Style::new());
+            }
+            v.assign_ids_topdown();
+            if let Some(dom) = open.get(&id) {
+                dom.patch(v);
+            } else {
+                // Mount at the top level rather than in whatever group is current
+                group::Group::set_current(None::<&group::Group>);
+                let root_id = v.node_id();
+                let dom = vdom::VirtualDom::new(v);
+                if let Some(win) = dom.widget_map.borrow().get(&root_id).map(|w| w.view()) {
+                    if let Some(win) = group::Group::from_dyn_widget(&win) {
+                        if win.children() == 1 {
+                            if let Some(mut first_child) = win.child(0) {
+                                first_child.resize(0, 0, win.w(), win.h());
+                            }
+                        }
+                    }
+                }
+                open.insert(id, dom);
+            }
+        }
+    }
     fn init(&self) -> (app::App, window::Window, tokio::runtime::Runtime) {
         let a = app::App::default();
         let binding = Settings::default();
         let settings: &Settings<Message> = self.settings.as_ref().unwrap_or(&binding);
 
-        fltk_theme::ColorTheme::from_colormap(settings.color_map).apply();
-
-        if let Some(color) = settings.background {
-            let c = color.to_rgb();
-            app::background(c.0, c.1, c.2);
-        }
-        if let Some(color) = settings.background2 {
-            let c = color.to_rgb();
-            app::background2(c.0, c.1, c.2);
-        }
-        if let Some(color) = settings.foreground {
-            let c = color.to_rgb();
-            app::foreground(c.0, c.1, c.2);
-        }
-        if let Some(color) = settings.inactive {
-            let c = color.to_rgb();
-            app::set_inactive_color(c.0, c.1, c.2);
-        }
-        if let Some(color) = settings.selection {
-            let c = color.to_rgb();
-            app::set_selection_color(c.0, c.1, c.2);
-        }
-        app::set_font_size(settings.font_size);
-
-        if let Some(theme) = settings.theme {
-            fltk_theme::WidgetTheme::new(theme).apply();
-        }
-
-        app::set_scheme(settings.scheme);
-
-        if let Some(font) = settings.font {
-            app::set_font(font);
-        }
-
-        app::set_menu_linespacing(settings.menu_linespacing);
+        Theme::from(settings).apply();
 
         let (w, h) = settings.size;
         let (x, y) = settings.pos;
@@ -134,6 +190,10 @@ impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> Applicat
         let (a, mut win, rt) = self.init();
 
         let mut t = init_fn();
+        let mut theme = self.theme.map(|f| f(&t));
+        if let Some(theme) = &theme {
+            theme.apply();
+        }
         let initial_vdom = self.view_(&t);
         let vdom = vdom::VirtualDom::new(initial_vdom);
 
@@ -191,56 +251,66 @@ impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> Applicat
 
         let (s, r) = app::channel::<Message>();
 
-        let mut current_sub: Option<Subscription<Message>> = None;
-        let mut current_hash: u64 = 0;
+        let mut windows = HashMap::new();
+        self.update_windows(&t, &mut windows);
+
+        let mut subscriptions = SubscriptionTracker::default();
+        let views = crate::task::ViewOps::default();
 
         rt.block_on(async {
             if let Some(sub_fn) = self.subscription {
-                let sub = sub_fn(&t);
-
-                let new_hash = spawn_or_reuse_subscription(&sub);
-
-                if new_hash != current_hash {
-                    let spawned_sub = spawn_new_subscription(sub, s);
-                    current_sub = Some(spawned_sub);
-                    current_hash = new_hash;
-                } else {
-                    current_sub = Some(sub);
-                }
+                subscriptions.update(sub_fn(&t), s);
             }
 
             let state = std::rc::Rc::new(std::cell::RefCell::new(self));
 
             while a.wait() {
+                // The loop never awaits otherwise, a current_thread runtime would
+                // never poll the spawned tasks
+                tokio::task::yield_now().await;
                 if let Some(msg) = r.recv() {
                     let mut st = state.borrow_mut();
 
                     vdom.dispatch(msg.clone());
+                    for dom in windows.values() {
+                        dom.dispatch(msg.clone());
+                    }
 
                     let command = st.update(&mut t, msg.clone());
-                    command.execute(s);
+                    command.execute_with_views(s, &views);
 
                     let new_vdom = st.view_(&t);
                     vdom.patch(new_vdom);
+                    st.update_windows(&t, &mut windows);
+                    if let Some(theme_fn) = st.theme {
+                        let new_theme = theme_fn(&t);
+                        if theme.as_ref() != Some(&new_theme) {
+                            new_theme.apply();
+                            theme = Some(new_theme);
+                        }
+                    }
+                    // Shows the patched widgets and any change of theme in every window
                     app::redraw();
 
                     if let Some(sub_fn) = st.subscription {
-                        let new_sub = sub_fn(&t);
-
-                        let new_hash = spawn_or_reuse_subscription(&new_sub);
-
-                        if new_hash != current_hash {
-                            cancel_subscription(current_sub.take());
-                            let spawned_sub = spawn_new_subscription(new_sub, s);
-                            current_sub = Some(spawned_sub);
-                            current_hash = new_hash;
-                        } else {
-                            current_sub = Some(new_sub);
-                        }
+                        subscriptions.update(sub_fn(&t), s);
+                    }
+                }
+                // Once patched, the views run the operations requested by the update,
+                // or by tasks which completed since
+                let ops = std::mem::take(&mut *views.lock().unwrap());
+                for op in &ops {
+                    vdom.run_view_op(op);
+                    for dom in windows.values() {
+                        dom.run_view_op(op);
                     }
                 }
             }
+            subscriptions.clear();
+            crate::task::abort_all();
         });
+        // Don't wait for closures which can't be told to stop and may still be running
+        rt.shutdown_background();
     }
 
     pub fn run(self)
@@ -14,3 +14,62 @@ pub use fltk::dialog::NativeFileChooser;
 pub use fltk::dialog::NativeFileChooserAction;
 pub use fltk::dialog::NativeFileChooserOptions;
 pub use fltk::dialog::NativeFileChooserType;
+
+use std::path::PathBuf;
+
+/// Options of the file dialogs shown by `Task::open_file`, `Task::save_file`
+/// and `Task::pick_folder`.
+#[derive(Debug, Clone, Default)]
+pub struct FileOptions {
+    title: Option<String>,
+    filter: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+}
+
+impl FileOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    /// A filter like "*.txt" or "Text\t*.{txt,md}", one per line.
+    pub fn filter(mut self, filter: &str) -> Self {
+        self.filter = Some(filter.to_string());
+        self
+    }
+    pub fn directory<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.directory = Some(dir.into());
+        self
+    }
+    /// The file name initially proposed by a save dialog.
+    pub fn file_name(mut self, name: &str) -> Self {
+        self.file_name = Some(name.to_string());
+        self
+    }
+}
+
+/// Shows a native file chooser, returning None when it was cancelled.
+pub(crate) fn choose_file(typ: NativeFileChooserType, opts: &FileOptions) -> Option<PathBuf> {
+    let mut nfc = NativeFileChooser::new(typ);
+    if typ == NativeFileChooserType::BrowseSaveFile {
+        nfc.set_option(NativeFileChooserOptions::SaveAsConfirm);
+    }
+    if let Some(title) = &opts.title {
+        nfc.set_title(title);
+    }
+    if let Some(filter) = &opts.filter {
+        nfc.set_filter(filter);
+    }
+    if let Some(dir) = &opts.directory {
+        let _ = nfc.set_directory(dir);
+    }
+    if let Some(name) = &opts.file_name {
+        nfc.set_preset_file(name);
+    }
+    nfc.show();
+    let p = nfc.filename();
+    (!p.as_os_str().is_empty()).then_some(p)
+}
@@ -1 +1,2 @@
 pub use fltk::enums::{Align, CallbackTrigger, Color, Event, Font, FrameType, Shortcut};
+pub use fltk::group::{GridAlign, ScrollType};
@@ -6,6 +6,18 @@ pub(crate) fn next_id() -> usize {
     NEXT_ID.fetch_add(1, Ordering::Relaxed)
 }
 
-pub(crate) fn reset_id() {
-    NEXT_ID.store(1, Ordering::Relaxed);
+/// Identifies a secondary window declared through `Application::windows`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowId(String);
+
+impl WindowId {
+    pub fn new(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<&str> for WindowId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
 }
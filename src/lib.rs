@@ -8,8 +8,10 @@ mod id;
 mod image;
 pub mod props;
 mod settings;
+pub mod style;
 pub mod subscription;
 pub mod task;
+pub mod testing;
 pub mod theme;
 pub mod utils;
 pub mod vdom;
@@ -19,8 +21,10 @@ pub mod widgets;
 
 use crate::application::Application;
 pub use fltk::app::Scheme;
+pub use id::WindowId;
 pub use image::Image;
 pub use settings::Settings;
+pub use style::{Style, StyleSheet};
 pub use subscription::Subscription;
 pub use task::Task;
 pub use view::View;
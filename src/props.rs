@@ -2,10 +2,28 @@ use crate::image::{Image, IMAGES};
 use crate::vnode::View;
 use fltk::{prelude::*, *};
 
+/// Where the children of a `Column` or `Row` are placed across its axis.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlexAlign {
+    Start,
+    Center,
+    End,
+    /// Fill the whole width of a column or height of a row.
+    #[default]
+    Stretch,
+}
+
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct WidgetProps {
     pub label: Option<String>,
     pub fixed: Option<i32>,
+    pub min_size: Option<i32>,
+    pub max_size: Option<i32>,
+    pub flex: Option<i32>,
+    pub align_self: Option<FlexAlign>,
+    pub cell: Option<(i32, i32)>,
+    pub span: Option<(i32, i32)>,
+    pub grid_align: Option<group::GridAlign>,
     pub boxtype: Option<enums::FrameType>,
     pub color: Option<enums::Color>,
     pub selection_color: Option<enums::Color>,
@@ -23,6 +41,8 @@ pub struct WidgetProps {
     pub h: Option<i32>,
     pub image: Option<Image>,
     pub deimage: Option<Image>,
+    pub key: Option<String>,
+    pub classes: Vec<String>,
 }
 
 #[derive(Default, Clone, Debug, PartialEq)]
@@ -43,6 +63,26 @@ impl<Message> Default for GroupProps<Message> {
     }
 }
 
+/// Puts the widget in its cell, if its parent is a `Grid`.
+fn set_grid_cell<W>(w: &mut W, wprops: &WidgetProps)
+where
+    W: WidgetExt + 'static,
+{
+    if let Some((row, col)) = wprops.cell {
+        if let Some(p) = w.parent() {
+            if let Some(mut p) = group::Grid::from_dyn_widget(&p) {
+                if let Some(mut wi) = widget::Widget::from_dyn_widget(w) {
+                    let (row, col) = (row.max(0) as usize, col.max(0) as usize);
+                    let (rows, cols) = wprops.span.unwrap_or((1, 1));
+                    let (rows, cols) = (rows.max(1) as usize, cols.max(1) as usize);
+                    let align = wprops.grid_align.unwrap_or(group::GridAlign::FILL);
+                    let _ = p.set_widget_ext(&mut wi, row..row + rows, col..col + cols, align);
+                }
+            }
+        }
+    }
+}
+
 pub fn set_wprops<W>(w: &mut W, wprops: &WidgetProps)
 where
     W: WidgetExt + 'static,
@@ -68,6 +108,8 @@ where
         }
     }
 
+    set_grid_cell(w, wprops);
+
     if let Some(c) = &wprops.color {
         w.set_color(*c);
     }
@@ -199,6 +241,13 @@ where
         }
     }
 
+    if old_wprops.cell != new_wprops.cell
+        || old_wprops.span != new_wprops.span
+        || old_wprops.grid_align != new_wprops.grid_align
+    {
+        set_grid_cell(w, new_wprops);
+    }
+
     if old_wprops.color != new_wprops.color {
         if let Some(c) = &new_wprops.color {
             w.set_color(*c);
@@ -0,0 +1,228 @@
+use crate::props::{TextProps, WidgetProps};
+use crate::vnode::{VNodeType, View};
+use fltk::enums;
+use std::collections::HashMap;
+
+/// Visual properties shared by the nodes of a class or of a type, see `StyleSheet`.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Style {
+    wprops: WidgetProps,
+    tprops: TextProps,
+}
+
+macro_rules! style_props {
+    ($props: ident, $($field: ident: $t: ty),*) => {
+        impl Style {
+            $(
+                pub fn $field(mut self, v: $t) -> Self {
+                    self.$props.$field = Some(v);
+                    self
+                }
+            )*
+        }
+    };
+}
+
+style_props!(wprops,
+    boxtype: enums::FrameType,
+    color: enums::Color,
+    selection_color: enums::Color,
+    label_color: enums::Color,
+    label_font: enums::Font,
+    label_size: i32,
+    align: enums::Align
+);
+style_props!(tprops,
+    text_color: enums::Color,
+    text_font: enums::Font,
+    text_size: i32
+);
+
+// Sets each of the fields which `$dst` doesn't have from `$src`
+macro_rules! fill {
+    ($dst: expr, $src: expr, $($field: ident),*) => {
+        $(
+            if $dst.$field.is_none() {
+                $dst.$field = $src.$field;
+            }
+        )*
+    };
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn fill_wprops(&self, wprops: &mut WidgetProps) {
+        fill!(
+            wprops,
+            self.wprops,
+            boxtype,
+            color,
+            selection_color,
+            label_color,
+            label_font,
+            label_size,
+            align
+        );
+    }
+
+    fn fill_tprops(&self, tprops: &mut TextProps) {
+        fill!(tprops, self.tprops, text_color, text_font, text_size);
+    }
+
+    /// The part of a node's style which its children inherit: the colours, fonts and
+    /// sizes of labels and text.
+    fn inheritable(wprops: &WidgetProps, tprops: &TextProps) -> Self {
+        let mut style = Self::new();
+        fill!(style.wprops, wprops, label_color, label_font, label_size);
+        style.tprops = tprops.clone();
+        style
+    }
+}
+
+/// Styles for node types and named classes, the classes of a node set with
+/// `HasProps::class`.
+///
+/// A node inherits the label and text colours, fonts and sizes of its parent, overridden
+/// by the style of its type, then by those of its classes in order, and its own props
+/// override them all. Applying a sheet to a subtree in the view function lets it override
+/// the application's sheet, which is applied last.
+#[derive(Default, Clone, Debug)]
+pub struct StyleSheet {
+    types: Vec<(VNodeType, Style)>,
+    classes: HashMap<String, Style>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the default style of the nodes of a type.
+    pub fn typ(mut self, typ: VNodeType, style: Style) -> Self {
+        self.types.retain(|(t, _)| *t != typ);
+        self.types.push((typ, style));
+        self
+    }
+    pub fn class(mut self, name: &str, style: Style) -> Self {
+        self.classes.insert(name.to_string(), style);
+        self
+    }
+    /// Styles the nodes of the view which don't set the props themselves.
+    pub fn apply<Message: 'static>(&self, mut view: View<Message>) -> View<Message> {
+        view.apply_styles(self, &Style::new());
+        view
+    }
+
+    /// Styles a node given the style inherited from its parent, returning the style its
+    /// children inherit.
+    pub(crate) fn style_node(
+        &self,
+        typ: &VNodeType,
+        wprops: &mut WidgetProps,
+        tprops: Option<&mut TextProps>,
+        inherited: &Style,
+    ) -> Style {
+        // The node's own props are filled first, then the later classes before the earlier
+        // ones, then the type and what is inherited
+        let classes = wprops
+            .classes
+            .iter()
+            .rev()
+            .filter_map(|c| self.classes.get(c))
+            .cloned()
+            .collect::<Vec<_>>();
+        let styles = classes
+            .iter()
+            .chain(self.types.iter().filter(|(t, _)| t == typ).map(|(_, s)| s))
+            .chain(std::iter::once(inherited));
+        // Nodes without text of their own still pass the text props down
+        let mut own_tprops = TextProps::default();
+        let tprops = tprops.unwrap_or(&mut own_tprops);
+        for style in styles {
+            style.fill_wprops(wprops);
+            style.fill_tprops(tprops);
+        }
+        Style::inheritable(wprops, tprops)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::*;
+    use fltk::enums::{Color, Font};
+
+    fn child(view: &mut View<()>, i: usize) -> &mut View<()> {
+        &mut view.gprops().unwrap().children[i]
+    }
+
+    #[test]
+    fn inherited_props_reach_the_children() {
+        let sheet = StyleSheet::new().class("dark", Style::new().label_color(Color::White));
+        let mut view = sheet.apply(
+            Column::new(&[Frame::new("a").view(), Frame::new("b").view()])
+                .class("dark")
+                .view(),
+        );
+        assert_eq!(child(&mut view, 0).wprops().label_color, Some(Color::White));
+        assert_eq!(child(&mut view, 1).wprops().label_color, Some(Color::White));
+    }
+
+    #[test]
+    fn only_label_and_text_props_are_inherited() {
+        let sheet = StyleSheet::new().class(
+            "dark",
+            Style::new().color(Color::Black).text_color(Color::White),
+        );
+        let mut view = sheet.apply(Column::new(&[Input::new("").view()]).class("dark").view());
+        let input = child(&mut view, 0);
+        assert_eq!(input.wprops().color, None);
+        assert_eq!(input.tprops().unwrap().text_color, Some(Color::White));
+    }
+
+    #[test]
+    fn type_overrides_inherited() {
+        let sheet = StyleSheet::new()
+            .class("dark", Style::new().label_color(Color::White))
+            .typ(VNodeType::Frame, Style::new().label_color(Color::Red));
+        let mut view = sheet.apply(Column::new(&[Frame::new("a").view()]).class("dark").view());
+        assert_eq!(child(&mut view, 0).wprops().label_color, Some(Color::Red));
+    }
+
+    #[test]
+    fn classes_override_type_in_order() {
+        let sheet = StyleSheet::new()
+            .typ(VNodeType::Frame, Style::new().label_color(Color::Red))
+            .class("blue", Style::new().label_color(Color::Blue))
+            .class("green", Style::new().label_color(Color::Green));
+        let mut view = sheet.apply(Frame::<()>::new("a").class("blue").class("green").view());
+        assert_eq!(view.wprops().label_color, Some(Color::Green));
+        let mut view = sheet.apply(Frame::<()>::new("a").class("blue").view());
+        assert_eq!(view.wprops().label_color, Some(Color::Blue));
+    }
+
+    #[test]
+    fn explicit_props_override_classes() {
+        let sheet =
+            StyleSheet::new().class("big", Style::new().label_size(20).label_font(Font::Courier));
+        let mut view = sheet.apply(Frame::<()>::new("a").class("big").label_size(12).view());
+        assert_eq!(view.wprops().label_size, Some(12));
+        assert_eq!(view.wprops().label_font, Some(Font::Courier));
+    }
+
+    #[test]
+    fn explicit_props_of_the_parent_are_inherited() {
+        let sheet = StyleSheet::new().typ(VNodeType::Column, Style::new().label_size(20));
+        let mut view = sheet.apply(
+            Column::new(&[Column::new(&[Frame::new("a").view()]).view()])
+                .label_size(14)
+                .view(),
+        );
+        let inner = child(&mut view, 0);
+        // The inner column's type style wins over what it inherits
+        assert_eq!(inner.wprops().label_size, Some(20));
+        assert_eq!(child(inner, 0).wprops().label_size, Some(20));
+    }
+}
@@ -4,6 +4,7 @@ use futures::{stream::BoxStream, StreamExt};
 use fxhash::FxHasher;
 use std::{
     any::TypeId,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     marker::PhantomData,
     sync::{
@@ -42,6 +43,7 @@ where
         recipe: Box<dyn Recipe<Output = M> + Send + Sync>,
         cancel_flag: Option<Arc<AtomicBool>>,
     },
+    Batch(Vec<Subscription<M>>),
 }
 
 impl<M> Subscription<M>
@@ -77,11 +79,23 @@ where
         Subscription::from_recipe(GenericAsyncRecipe::new(f))
     }
 
-    pub fn cancelable(mut self, flag: Arc<AtomicBool>) -> Self {
-        if let Subscription::Recipe { cancel_flag, .. } = &mut self {
-            *cancel_flag = Some(flag);
+    pub fn batch(subs: Vec<Subscription<M>>) -> Self {
+        Subscription::Batch(subs)
+    }
+
+    pub fn cancelable(self, flag: Arc<AtomicBool>) -> Self {
+        match self {
+            Subscription::None => Subscription::None,
+            Subscription::Recipe { recipe, .. } => Subscription::Recipe {
+                recipe,
+                cancel_flag: Some(flag),
+            },
+            Subscription::Batch(subs) => Subscription::Batch(
+                subs.into_iter()
+                    .map(|sub| sub.cancelable(flag.clone()))
+                    .collect(),
+            ),
         }
-        self
     }
 
     pub fn map<N, F>(self, f: F) -> Subscription<N>
@@ -101,6 +115,9 @@ where
                 }),
                 cancel_flag,
             },
+            Subscription::Batch(subs) => {
+                Subscription::Batch(subs.into_iter().map(|sub| sub.map(f.clone())).collect())
+            }
         }
     }
 }
@@ -119,65 +136,123 @@ impl Subscription<Event> {
     }
 }
 
-pub fn batch<M: Send + Sync>(subs: Vec<Subscription<M>>) -> Vec<Subscription<M>> {
-    subs
+pub fn batch<M: Clone + Send + Sync>(subs: Vec<Subscription<M>>) -> Subscription<M> {
+    Subscription::batch(subs)
 }
 
-pub(crate) fn spawn_new_subscription<M>(sub: Subscription<M>, sender: Sender<M>) -> Subscription<M>
+type RunningRecipe<M> = (
+    u64,
+    Box<dyn Recipe<Output = M> + Send + Sync>,
+    Option<Arc<AtomicBool>>,
+);
+
+fn flatten<M>(sub: Subscription<M>, out: &mut Vec<RunningRecipe<M>>)
 where
     M: Clone + Send + Sync + 'static,
 {
     match sub {
-        Subscription::None => Subscription::None,
+        Subscription::None => (),
         Subscription::Recipe {
             recipe,
             cancel_flag,
         } => {
-            let local_cancel = cancel_flag.clone();
-
-            let mut stream = recipe.stream();
+            let mut hasher = FxHasher::default();
+            recipe.hash(&mut hasher);
+            out.push((hasher.finish(), recipe, cancel_flag));
+        }
+        Subscription::Batch(subs) => {
+            for sub in subs {
+                flatten(sub, out);
+            }
+        }
+    }
+}
 
-            task::spawn(async move {
-                while let Some(msg) = stream.next().await {
-                    if let Some(cf) = &local_cancel {
-                        if cf.load(std::sync::atomic::Ordering::Relaxed) {
-                            break;
-                        }
-                    }
-                    sender.send(msg.clone());
-                }
-            });
+struct RunningHandle {
+    abort: task::AbortHandle,
+    cancel_flag: Option<Arc<AtomicBool>>,
+}
 
-            Subscription::None
+impl RunningHandle {
+    fn stop(&self) {
+        if let Some(cf) = &self.cancel_flag {
+            cf.store(true, Ordering::Relaxed);
         }
+        self.abort.abort();
     }
 }
 
-pub(crate) fn cancel_subscription<M>(sub: Option<Subscription<M>>)
-where
-    M: Clone + Send + Sync + 'static,
-{
-    if let Some(Subscription::Recipe {
-        cancel_flag: Some(cf),
-        ..
-    }) = sub
+/// Keeps track of the running recipes by hash, so that only the recipes which
+/// appear or disappear between two calls are started or aborted.
+#[derive(Default)]
+pub(crate) struct SubscriptionTracker {
+    running: HashMap<u64, RunningHandle>,
+}
+
+impl SubscriptionTracker {
+    pub(crate) fn update<M>(&mut self, sub: Subscription<M>, sender: Sender<M>)
+    where
+        M: Clone + Send + Sync + 'static,
     {
-        cf.store(true, std::sync::atomic::Ordering::Relaxed);
+        let mut recipes = vec![];
+        flatten(sub, &mut recipes);
+        let hashes: HashSet<u64> = recipes.iter().map(|(hash, ..)| *hash).collect();
+
+        self.running.retain(|hash, handle| {
+            let keep = hashes.contains(hash);
+            if !keep {
+                handle.stop();
+            }
+            keep
+        });
+
+        for (hash, recipe, cancel_flag) in recipes {
+            if self.running.contains_key(&hash) {
+                continue;
+            }
+            let abort = spawn_recipe(recipe, cancel_flag.clone(), sender);
+            self.running
+                .insert(hash, RunningHandle { abort, cancel_flag });
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for (_, handle) in self.running.drain() {
+            handle.stop();
+        }
+    }
+}
+
+/// Aborts the task feeding a recipe's stream once the stream itself is dropped.
+struct AbortOnDrop(task::AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
     }
 }
 
-pub(crate) fn spawn_or_reuse_subscription<M>(sub: &Subscription<M>) -> u64
+fn spawn_recipe<M>(
+    recipe: Box<dyn Recipe<Output = M> + Send + Sync>,
+    cancel_flag: Option<Arc<AtomicBool>>,
+    sender: Sender<M>,
+) -> task::AbortHandle
 where
     M: Clone + Send + Sync + 'static,
 {
-    match sub {
-        Subscription::None => 0,
-        Subscription::Recipe { recipe, .. } => {
-            let mut hasher = FxHasher::default();
-            recipe.hash(&mut hasher);
-            hasher.finish()
+    let mut stream = recipe.stream();
+
+    task::spawn(async move {
+        while let Some(msg) = stream.next().await {
+            if let Some(cf) = &cancel_flag {
+                if cf.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+            sender.send(msg.clone());
         }
-    }
+    })
+    .abort_handle()
 }
 
 struct EveryRecipe {
@@ -308,11 +383,11 @@ where
         let (tx, rx) = unbounded_channel::<M>();
         let mut f_opt = self.f;
         let s = stream! {
-            if let Some(f) = f_opt.take() {
-                tokio::task::spawn(async move {
+            let _guard = f_opt.take().map(|f| {
+                AbortOnDrop(tokio::task::spawn(async move {
                     f(tx).await;
-                });
-            }
+                }).abort_handle())
+            });
             let mut rx_stream = UnboundedReceiverStream::new(rx);
             while let Some(msg) = rx_stream.next().await {
                 yield msg;
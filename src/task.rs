@@ -1,14 +1,180 @@
+use crate::dialog::{self, FileOptions, NativeFileChooserType};
+use crate::theme::Theme;
 use fltk::app::Sender;
 use std::future::Future;
+use std::path::PathBuf;
 use std::{
     pin::Pin,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, LazyLock, Mutex,
     },
 };
 use tokio::task;
 
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+type MainJob = Box<dyn FnOnce() + Send>;
+
+/// Work on the mounted views, which the application runs once they are patched.
+pub(crate) enum ViewOp {
+    ScrollToNode(String),
+}
+
+/// The view operations requested by tasks, waiting for the application to run them.
+pub(crate) type ViewOps = Arc<Mutex<Vec<ViewOp>>>;
+
+/// Where a running task delivers its messages and its exit requests.
+struct Outlet<M> {
+    send: Arc<dyn Fn(M) + Send + Sync>,
+    exit: Arc<dyn Fn() + Send + Sync>,
+    main: Arc<dyn Fn(MainJob) + Send + Sync>,
+    view: Arc<dyn Fn(ViewOp) + Send + Sync>,
+    wake: Arc<dyn Fn() + Send + Sync>,
+    token: AbortToken,
+}
+
+impl<M> Clone for Outlet<M> {
+    fn clone(&self) -> Self {
+        Self {
+            send: self.send.clone(),
+            exit: self.exit.clone(),
+            main: self.main.clone(),
+            view: self.view.clone(),
+            wake: self.wake.clone(),
+            token: self.token.clone(),
+        }
+    }
+}
+
+impl<M: Send + Sync + 'static> Outlet<M> {
+    fn from_sender(sender: Sender<M>, views: ViewOps) -> Self {
+        Self {
+            send: Arc::new(move |msg| sender.send(msg)),
+            // quitting hides the windows, which has to happen on the main thread
+            exit: Arc::new(|| fltk::app::awake_callback(fltk::app::quit)),
+            // awake callbacks run once the main loop is back from the current update and patch
+            main: Arc::new(|job: MainJob| {
+                let mut job = Some(job);
+                fltk::app::awake_callback(move || {
+                    if let Some(job) = job.take() {
+                        job();
+                    }
+                });
+            }),
+            view: Arc::new(move |op| {
+                views.lock().unwrap().push(op);
+                // the main loop runs them once it is back from waiting
+                fltk::app::awake();
+            }),
+            wake: Arc::new(fltk::app::awake),
+            token: AbortToken::default(),
+        }
+    }
+
+    fn guarded(self, flag: Arc<AtomicBool>) -> Self {
+        let send = self.send;
+        Self {
+            send: Arc::new(move |msg| {
+                if !flag.load(Ordering::Relaxed) {
+                    send(msg);
+                }
+            }),
+            exit: self.exit,
+            main: self.main,
+            view: self.view,
+            wake: self.wake,
+            token: self.token,
+        }
+    }
+}
+
+/// What running a task without the main loop left undone.
+pub(crate) struct Completion {
+    pub exited: bool,
+    /// The number of jobs for the main thread which were dropped.
+    pub main_thread: usize,
+}
+
+/// Lets a blocking closure know that its task was aborted and that its result
+/// will be discarded.
+#[derive(Clone, Default)]
+pub struct AbortToken(Arc<AtomicBool>);
+
+impl AbortToken {
+    pub fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Aborts a task created with `Task::abortable`.
+#[derive(Clone, Default)]
+pub struct TaskHandle {
+    token: AbortToken,
+    handle: Arc<Mutex<Option<task::AbortHandle>>>,
+}
+
+impl TaskHandle {
+    pub fn abort(&self) {
+        self.token.0.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+            handle.abort();
+        }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.token.is_aborted()
+    }
+}
+
+/// Runs blocking work, then wakes the main loop so that the future awaiting it is polled.
+fn wake_after<T>(wake: &Arc<dyn Fn() + Send + Sync>, func: impl FnOnce() -> T) -> T {
+    let val = func();
+    wake();
+    val
+}
+
+/// The work still running, along with the tokens telling it that it was aborted.
+#[derive(Default)]
+struct Running {
+    futures: Vec<(task::AbortHandle, AbortToken)>,
+    threads: Vec<(std::thread::JoinHandle<()>, AbortToken)>,
+}
+
+static RUNNING: LazyLock<Mutex<Running>> = LazyLock::new(Default::default);
+
+fn spawn_tracked(fut: BoxFuture, token: AbortToken) -> task::JoinHandle<()> {
+    let join = task::spawn(fut);
+    let mut running = RUNNING.lock().unwrap();
+    running.futures.retain(|(h, _)| !h.is_finished());
+    running.futures.push((join.abort_handle(), token));
+    join
+}
+
+fn spawn_thread_tracked(func: impl FnOnce() + Send + 'static, token: AbortToken) {
+    let join = std::thread::spawn(func);
+    let mut running = RUNNING.lock().unwrap();
+    running.threads.retain(|(h, _)| !h.is_finished());
+    running.threads.push((join, token));
+}
+
+/// Aborts every task still running, called when the application exits.
+///
+/// Every token is set first, so that blocking closures stop along with the futures
+/// awaiting them, then the threads running blocking closures are waited for.
+pub(crate) fn abort_all() {
+    let Running { futures, threads } = std::mem::take(&mut *RUNNING.lock().unwrap());
+    let tokens = futures.iter().map(|(_, t)| t);
+    for token in tokens.chain(threads.iter().map(|(_, t)| t)) {
+        token.0.store(true, Ordering::Relaxed);
+    }
+    for (handle, _) in futures {
+        handle.abort();
+    }
+    for (join, _) in threads {
+        let _ = join.join();
+    }
+}
+
 pub struct Task<M: Send + Sync> {
     executor: Executor<M>,
     cancel_flag: Option<Arc<AtomicBool>>,
@@ -33,9 +199,14 @@ impl<Message: Send + Sync + 'static, E> From<Result<Task<Message>, E>> for Task<
 enum Executor<M: Send + Sync> {
     None,
     Exit,
+    Done(M),
+    View(ViewOp),
     SyncFn(fn() -> M),
     SyncClosure(Box<dyn FnOnce() -> M + Send>),
+    Blocking(Box<dyn FnOnce(&AbortToken) -> M + Send>),
     Async(Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = M> + Send + 'static>> + Send>),
+    MainThread(Box<dyn FnOnce() -> Option<M> + Send>),
+    Compound(Box<dyn FnOnce(Outlet<M>) -> BoxFuture + Send>),
 }
 
 impl<M> Task<M>
@@ -56,6 +227,14 @@ where
         }
     }
 
+    /// Delivers the message right away, without running anything.
+    pub fn done(message: M) -> Self {
+        Self {
+            executor: Executor::Done(message),
+            cancel_flag: None,
+        }
+    }
+
     pub fn perform_simple(func: fn() -> M) -> Self {
         Self {
             executor: Executor::SyncFn(func),
@@ -73,6 +252,18 @@ where
         }
     }
 
+    /// Like `perform`, the closure receiving a token telling it whether the task
+    /// was aborted, so that long computations can bail out early.
+    pub fn perform_with_token<F>(func: F) -> Self
+    where
+        F: FnOnce(&AbortToken) -> M + Send + 'static,
+    {
+        Self {
+            executor: Executor::Blocking(Box::new(func)),
+            cancel_flag: None,
+        }
+    }
+
     pub fn perform_async<Fut, F>(future: F) -> Self
     where
         Fut: Future<Output = M> + Send + 'static,
@@ -84,10 +275,123 @@ where
         }
     }
 
+    /// Runs the closure on the main thread once the current update and patch are done,
+    /// as needed by anything showing a window of its own, and delivers its result.
+    pub fn on_main_thread<F>(func: F) -> Self
+    where
+        F: FnOnce() -> M + Send + 'static,
+    {
+        Self {
+            executor: Executor::MainThread(Box::new(move || Some(func()))),
+            cancel_flag: None,
+        }
+    }
+
+    /// Applies a theme once the current update is done and redraws all the windows.
+    pub fn set_theme(theme: Theme) -> Self {
+        Self {
+            executor: Executor::MainThread(Box::new(move || {
+                theme.apply();
+                fltk::app::redraw();
+                None
+            })),
+            cancel_flag: None,
+        }
+    }
+
+    /// Shows a message box, delivering nothing.
+    pub fn alert(text: &str) -> Self {
+        let text = text.to_string();
+        Self {
+            executor: Executor::MainThread(Box::new(move || {
+                dialog::alert_default(&text);
+                None
+            })),
+            cancel_flag: None,
+        }
+    }
+
+    /// Scrolls the `Scroll` nearest to the node with the key so that the node is fully
+    /// visible, once the current patch is done. Delivers nothing.
+    pub fn scroll_to_node(key: &str) -> Self {
+        Self {
+            executor: Executor::View(ViewOp::ScrollToNode(key.to_string())),
+            cancel_flag: None,
+        }
+    }
+
+    fn compound<F>(run: F) -> Self
+    where
+        F: FnOnce(Outlet<M>) -> BoxFuture + Send + 'static,
+    {
+        Self {
+            executor: Executor::Compound(Box::new(run)),
+            cancel_flag: None,
+        }
+    }
+
+    /// Runs all the tasks concurrently.
+    pub fn batch(tasks: Vec<Task<M>>) -> Self {
+        Self::compound(move |out| {
+            Box::pin(async move {
+                futures::future::join_all(tasks.into_iter().map(|t| t.run(out.clone()))).await;
+            })
+        })
+    }
+
+    /// Runs the tasks one after the other, each one starting once the previous one is done.
+    pub fn sequence(tasks: Vec<Task<M>>) -> Self {
+        Self::compound(move |out| {
+            Box::pin(async move {
+                for t in tasks {
+                    t.run(out.clone()).await;
+                }
+            })
+        })
+    }
+
+    /// Runs `next` once this task is done.
+    pub fn chain(self, next: Task<M>) -> Self {
+        Self::sequence(vec![self, next])
+    }
+
+    /// Passes the output of this task to `f` and runs the returned task instead of
+    /// delivering the output. If this task yields several messages, as a batch would,
+    /// only the first one is passed to `f`.
+    pub fn then<F>(self, f: F) -> Self
+    where
+        F: FnOnce(M) -> Task<M> + Send + 'static,
+    {
+        Self::compound(move |out| {
+            Box::pin(async move {
+                let first = Arc::new(Mutex::new(None));
+                let inner = Outlet {
+                    send: Arc::new({
+                        let first = first.clone();
+                        move |msg| {
+                            first.lock().unwrap().get_or_insert(msg);
+                        }
+                    }),
+                    exit: out.exit.clone(),
+                    main: out.main.clone(),
+                    view: out.view.clone(),
+                    wake: out.wake.clone(),
+                    token: out.token.clone(),
+                };
+                self.run(inner).await;
+                let first = first.lock().unwrap().take();
+                if let Some(msg) = first {
+                    f(msg).run(out).await;
+                }
+            })
+        })
+    }
+
+    /// Maps the output of the task, every message of a task yielding several ones.
     pub fn map<N, F>(self, mapper: F) -> Task<N>
     where
         N: Send + Sync + 'static,
-        F: FnOnce(M) -> N + Send + 'static,
+        F: Fn(M) -> N + Send + Sync + 'static,
     {
         match self.executor {
             Executor::None => Task {
@@ -98,6 +402,14 @@ where
                 executor: Executor::Exit,
                 cancel_flag: self.cancel_flag,
             },
+            Executor::Done(msg) => Task {
+                executor: Executor::Done(mapper(msg)),
+                cancel_flag: self.cancel_flag,
+            },
+            Executor::View(op) => Task {
+                executor: Executor::View(op),
+                cancel_flag: self.cancel_flag,
+            },
             Executor::SyncFn(func) => Task {
                 executor: Executor::SyncClosure(Box::new(move || mapper(func()))),
                 cancel_flag: self.cancel_flag,
@@ -106,6 +418,10 @@ where
                 executor: Executor::SyncClosure(Box::new(move || mapper(func()))),
                 cancel_flag: self.cancel_flag,
             },
+            Executor::Blocking(func) => Task {
+                executor: Executor::Blocking(Box::new(move |token| mapper(func(token)))),
+                cancel_flag: self.cancel_flag,
+            },
             Executor::Async(fut) => Task {
                 executor: Executor::Async(Box::new(move || {
                     Box::pin(async move {
@@ -115,55 +431,186 @@ where
                 })),
                 cancel_flag: self.cancel_flag,
             },
+            Executor::MainThread(func) => Task {
+                executor: Executor::MainThread(Box::new(move || func().map(mapper))),
+                cancel_flag: self.cancel_flag,
+            },
+            Executor::Compound(run) => Task {
+                executor: Executor::Compound(Box::new(move |out: Outlet<N>| {
+                    let send = out.send;
+                    run(Outlet {
+                        send: Arc::new(move |msg| send(mapper(msg))),
+                        exit: out.exit,
+                        main: out.main,
+                        view: out.view,
+                        wake: out.wake,
+                        token: out.token,
+                    })
+                })),
+                cancel_flag: self.cancel_flag,
+            },
+        }
+    }
+
+    fn run(self, out: Outlet<M>) -> BoxFuture {
+        let out = match self.cancel_flag {
+            Some(flag) => out.guarded(flag),
+            None => out,
+        };
+        match self.executor {
+            Executor::None => Box::pin(async {}),
+            Executor::Exit => Box::pin(async move { (out.exit)() }),
+            Executor::Done(msg) => Box::pin(async move { (out.send)(msg) }),
+            Executor::View(op) => Box::pin(async move { (out.view)(op) }),
+            Executor::SyncFn(func) => Box::pin(async move {
+                let wake = out.wake.clone();
+                if let Ok(val) = task::spawn_blocking(move || wake_after(&wake, func)).await {
+                    (out.send)(val);
+                }
+            }),
+            Executor::SyncClosure(func) => Box::pin(async move {
+                let wake = out.wake.clone();
+                if let Ok(val) = task::spawn_blocking(move || wake_after(&wake, func)).await {
+                    (out.send)(val);
+                }
+            }),
+            Executor::Blocking(func) => Box::pin(async move {
+                let wake = out.wake.clone();
+                let token = out.token.clone();
+                if let Ok(val) =
+                    task::spawn_blocking(move || wake_after(&wake, || func(&token))).await
+                {
+                    (out.send)(val);
+                }
+            }),
+            Executor::Async(fut) => Box::pin(async move { (out.send)(fut().await) }),
+            Executor::MainThread(func) => Box::pin(async move {
+                let (tx, rx) = futures::channel::oneshot::channel();
+                (out.main)(Box::new(move || {
+                    let _ = tx.send(func());
+                }));
+                if let Ok(Some(val)) = rx.await {
+                    (out.send)(val);
+                }
+            }),
+            Executor::Compound(run) => run(out),
         }
     }
 
+    /// Runs the task, delivering its messages through `sender`. Operations on the views,
+    /// like `scroll_to_node`, are only run by the application.
     pub fn execute(self, sender: Sender<M>) {
-        let canceled = self.cancel_flag.clone();
+        self.execute_with_views(sender, &ViewOps::default());
+    }
+
+    pub(crate) fn execute_with_views(self, sender: Sender<M>, views: &ViewOps) {
+        let out = match self.cancel_flag {
+            Some(flag) => Outlet::from_sender(sender, views.clone()).guarded(flag),
+            None => Outlet::from_sender(sender, views.clone()),
+        };
+        // Only the futures go through the runtime, the rest doesn't depend on it being
+        // polled, which a current_thread runtime only is between two events
         match self.executor {
             Executor::None => {}
             Executor::Exit => {
                 fltk::app::quit();
             }
+            Executor::Done(msg) => (out.send)(msg),
+            Executor::View(op) => (out.view)(op),
             Executor::SyncFn(func) => {
-                std::thread::spawn(move || {
-                    let val = func();
-                    if canceled
-                        .as_ref()
-                        .map(|f| !f.load(Ordering::Relaxed))
-                        .unwrap_or(true)
-                    {
-                        sender.send(val);
-                    }
-                });
+                std::thread::spawn(move || (out.send)(func()));
             }
             Executor::SyncClosure(func) => {
-                std::thread::spawn(move || {
-                    let val = func();
-                    if canceled
-                        .as_ref()
-                        .map(|f| !f.load(Ordering::Relaxed))
-                        .unwrap_or(true)
-                    {
-                        sender.send(val);
-                    }
-                });
+                std::thread::spawn(move || (out.send)(func()));
+            }
+            Executor::Blocking(func) => {
+                let token = out.token.clone();
+                spawn_thread_tracked(move || (out.send)(func(&out.token)), token);
             }
-            Executor::Async(fut) => {
-                task::spawn(async move {
-                    let val = fut().await;
-                    if canceled
-                        .as_ref()
-                        .map(|f| !f.load(Ordering::Relaxed))
-                        .unwrap_or(true)
-                    {
-                        sender.send(val);
+            Executor::MainThread(func) => {
+                let main = out.main.clone();
+                main(Box::new(move || {
+                    if let Some(val) = func() {
+                        (out.send)(val);
                     }
-                });
+                }));
+            }
+            executor => {
+                let task = Task {
+                    executor,
+                    cancel_flag: None,
+                };
+                let token = out.token.clone();
+                spawn_tracked(task.run(out), token);
             }
         }
     }
 
+    /// Returns a task which can be aborted through the returned handle, whether it is
+    /// still pending or already running. An aborted task never delivers its result.
+    pub fn abortable(self) -> (Self, TaskHandle) {
+        let handle = TaskHandle::default();
+        let h = handle.clone();
+        let task = Self::compound(move |out| {
+            Box::pin(async move {
+                if h.is_aborted() {
+                    return;
+                }
+                let join = spawn_tracked(
+                    self.run(Outlet {
+                        token: h.token.clone(),
+                        ..out
+                    }),
+                    h.token.clone(),
+                );
+                *h.handle.lock().unwrap() = Some(join.abort_handle());
+                // abort() may have been called before the handle was stored
+                if h.is_aborted() {
+                    join.abort();
+                }
+                let _ = join.await;
+            })
+        });
+        (task, handle)
+    }
+
+    /// Runs the task to completion on the calling thread, pushing its results into `out`.
+    /// Work meant for the main thread, like dialogs, is dropped and delivers nothing.
+    pub(crate) fn execute_blocking(
+        self,
+        rt: &tokio::runtime::Runtime,
+        out: &mut Vec<M>,
+    ) -> Completion {
+        let results = Arc::new(Mutex::new(vec![]));
+        let exited = Arc::new(AtomicBool::new(false));
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let outlet = Outlet {
+            send: Arc::new({
+                let results = results.clone();
+                move |msg| results.lock().unwrap().push(msg)
+            }),
+            exit: Arc::new({
+                let exited = exited.clone();
+                move || exited.store(true, Ordering::Relaxed)
+            }),
+            main: Arc::new({
+                let dropped = dropped.clone();
+                move |_job| {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }),
+            view: Arc::new(|_| {}),
+            wake: Arc::new(|| {}),
+            token: AbortToken::default(),
+        };
+        rt.block_on(self.run(outlet));
+        out.append(&mut results.lock().unwrap());
+        Completion {
+            exited: exited.load(Ordering::Relaxed),
+            main_thread: dropped.load(Ordering::Relaxed),
+        }
+    }
+
     pub fn cancelable(mut self, flag: Arc<AtomicBool>) -> Self {
         self.cancel_flag = Some(flag);
         self
@@ -174,25 +621,80 @@ pub fn batch<M>(tasks: Vec<Task<M>>, sender: Sender<M>)
 where
     M: Send + Sync + 'static,
 {
-    for task in tasks {
-        task.execute(sender);
-    }
+    Task::batch(tasks).execute(sender);
 }
 
+/// Runs the tasks concurrently and collects their results in order,
+/// skipping the tasks which were cancelled or produced nothing.
 pub fn join<M>(tasks: Vec<Task<M>>) -> Task<Vec<M>>
 where
     M: Send + Sync + 'static,
 {
-    Task::perform_async(move || async move {
-        let mut results = Vec::with_capacity(tasks.len());
-        for t in tasks {
-            let (tx, rx) = fltk::app::channel::<M>();
-            t.execute(tx);
-            if let Some(msg) = rx.recv() {
-                results.push(msg);
-            }
-            // If the task was canceled and sent nothing, skip to avoid panics.
-        }
-        results
+    join_optional(tasks).map(|results| results.into_iter().flatten().collect())
+}
+
+/// Runs the tasks concurrently and collects their results in order,
+/// with `None` for the tasks which were cancelled or produced nothing.
+pub fn join_optional<M>(tasks: Vec<Task<M>>) -> Task<Vec<Option<M>>>
+where
+    M: Send + Sync + 'static,
+{
+    Task::compound(move |out| {
+        Box::pin(async move {
+            let slots: Vec<_> = tasks.iter().map(|_| Arc::new(Mutex::new(None))).collect();
+            let runs = tasks.into_iter().zip(slots.iter()).map(|(t, slot)| {
+                let slot = slot.clone();
+                t.run(Outlet {
+                    send: Arc::new(move |msg| {
+                        slot.lock().unwrap().get_or_insert(msg);
+                    }),
+                    exit: out.exit.clone(),
+                    main: out.main.clone(),
+                    view: out.view.clone(),
+                    wake: out.wake.clone(),
+                    token: out.token.clone(),
+                })
+            });
+            futures::future::join_all(runs).await;
+            let results = slots
+                .into_iter()
+                .map(|slot| slot.lock().unwrap().take())
+                .collect();
+            (out.send)(results);
+        })
     })
 }
+
+impl Task<Option<PathBuf>> {
+    /// Asks for a file to open, delivering None when the dialog is cancelled.
+    pub fn open_file(opts: FileOptions) -> Self {
+        Task::on_main_thread(move || dialog::choose_file(NativeFileChooserType::BrowseFile, &opts))
+    }
+
+    /// Asks for a file to save to, confirming the overwrite of existing files.
+    pub fn save_file(opts: FileOptions) -> Self {
+        Task::on_main_thread(move || {
+            dialog::choose_file(NativeFileChooserType::BrowseSaveFile, &opts)
+        })
+    }
+
+    pub fn pick_folder(opts: FileOptions) -> Self {
+        Task::on_main_thread(move || dialog::choose_file(NativeFileChooserType::BrowseDir, &opts))
+    }
+}
+
+impl Task<bool> {
+    /// Asks a yes/no question, delivering true for yes.
+    pub fn confirm(text: &str) -> Self {
+        let text = text.to_string();
+        Task::on_main_thread(move || dialog::choice_default(&text, "No", "Yes", "") == Some(1))
+    }
+}
+
+impl Task<Option<String>> {
+    /// Asks for a line of text, delivering None when the dialog is cancelled.
+    pub fn input(text: &str, default: &str) -> Self {
+        let (text, default) = (text.to_string(), default.to_string());
+        Task::on_main_thread(move || dialog::input_default(&text, &default))
+    }
+}
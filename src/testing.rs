@@ -0,0 +1,260 @@
+//! Headless driver for unit-testing the update/view cycle of an application.
+//!
+//! ```rust,no_run
+//! use flemish::{testing::TestApp, view::*};
+//!
+//! #[derive(Default)]
+//! struct Counter {
+//!     value: i32,
+//! }
+//!
+//! #[derive(Debug, Clone)]
+//! enum Message {
+//!     Increment,
+//! }
+//!
+//! fn update(c: &mut Counter, _msg: Message) {
+//!     c.value += 1;
+//! }
+//!
+//! fn view(c: &Counter) -> View<Message> {
+//!     Column::new(&[
+//!         Button::new("+", Message::Increment).view(),
+//!         Frame::new(&c.value.to_string()).view(),
+//!     ])
+//!     .view()
+//! }
+//!
+//! let mut app = TestApp::new(update, view);
+//! app.send(Message::Increment).send(Message::Increment);
+//! assert_eq!(app.state().value, 2);
+//! assert!(app.find_by_label("2").is_some());
+//! ```
+
+use crate::task::Task;
+pub use crate::vnode::AnyNode;
+use crate::vnode::{VNodeType, View};
+use std::collections::VecDeque;
+
+pub struct TestApp<T, Message: Send + Sync + 'static, U: Into<Task<Message>>> {
+    state: T,
+    update_fn: fn(&mut T, Message) -> U,
+    view_fn: fn(&T) -> View<Message>,
+    view: View<Message>,
+    history: Vec<Message>,
+    exited: bool,
+    main_thread: usize,
+    rt: tokio::runtime::Runtime,
+}
+
+impl<T: Default, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>>
+    TestApp<T, Message, U>
+{
+    pub fn new(update_fn: fn(&mut T, Message) -> U, view_fn: fn(&T) -> View<Message>) -> Self {
+        Self::with_state(T::default(), update_fn, view_fn)
+    }
+}
+
+impl<T, Message: Clone + Send + Sync + 'static, U: Into<Task<Message>>> TestApp<T, Message, U> {
+    pub fn with_state(
+        state: T,
+        update_fn: fn(&mut T, Message) -> U,
+        view_fn: fn(&T) -> View<Message>,
+    ) -> Self {
+        let view = view_fn(&state);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        Self {
+            state,
+            update_fn,
+            view_fn,
+            view,
+            history: vec![],
+            exited: false,
+            main_thread: 0,
+            rt,
+        }
+    }
+
+    /// Feeds a message to update, then runs the returned tasks and any message they produce
+    /// until no work is left, and finally rebuilds the view. Tasks meant for the main thread,
+    /// like dialogs, need the main loop and are dropped, see `main_thread_tasks`.
+    pub fn send(&mut self, message: Message) -> &mut Self {
+        let mut queue = VecDeque::from([message]);
+        while let Some(msg) = queue.pop_front() {
+            self.history.push(msg.clone());
+            let task: Task<Message> = (self.update_fn)(&mut self.state, msg).into();
+            self.run_task(task, &mut queue);
+        }
+        self.view = (self.view_fn)(&self.state);
+        self
+    }
+
+    pub fn send_all<I: IntoIterator<Item = Message>>(&mut self, messages: I) -> &mut Self {
+        for msg in messages {
+            self.send(msg);
+        }
+        self
+    }
+
+    fn run_task(&mut self, task: Task<Message>, queue: &mut VecDeque<Message>) {
+        let mut out = vec![];
+        let done = task.execute_blocking(&self.rt, &mut out);
+        self.exited |= done.exited;
+        self.main_thread += done.main_thread;
+        queue.extend(out);
+    }
+
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut T {
+        &mut self.state
+    }
+
+    pub fn view(&mut self) -> &mut View<Message> {
+        &mut self.view
+    }
+
+    /// All the messages processed so far, including those produced by tasks.
+    pub fn history(&self) -> &[Message] {
+        &self.history
+    }
+
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+
+    /// The number of tasks meant for the main thread which were dropped, delivering nothing.
+    pub fn main_thread_tasks(&self) -> usize {
+        self.main_thread
+    }
+
+    /// Returns the first node, in depth-first order, matching the predicate. The subtrees
+    /// of `Map` nodes are searched too, which is why nodes are seen as `AnyNode`s.
+    pub fn find<F: FnMut(&mut dyn AnyNode) -> bool>(
+        &mut self,
+        mut pred: F,
+    ) -> Option<&mut dyn AnyNode> {
+        find_node(&mut self.view, &mut pred)
+    }
+
+    pub fn find_by_type(&mut self, typ: VNodeType) -> Option<&mut dyn AnyNode> {
+        self.find(|n| *n.typ() == typ)
+    }
+
+    pub fn find_by_label(&mut self, label: &str) -> Option<&mut dyn AnyNode> {
+        self.find(|n| n.wprops().label.as_deref() == Some(label))
+    }
+
+    pub fn find_by_key(&mut self, key: &str) -> Option<&mut dyn AnyNode> {
+        self.find(|n| n.wprops().key.as_deref() == Some(key))
+    }
+
+    pub fn count<F: FnMut(&mut dyn AnyNode) -> bool>(&mut self, mut pred: F) -> usize {
+        count_nodes(&mut self.view, &mut pred)
+    }
+}
+
+fn find_node<'a, F: FnMut(&mut dyn AnyNode) -> bool>(
+    node: &'a mut dyn AnyNode,
+    pred: &mut F,
+) -> Option<&'a mut dyn AnyNode> {
+    if pred(node) {
+        return Some(node);
+    }
+    for ch in node.children() {
+        if let Some(found) = find_node(ch, pred) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn count_nodes<F: FnMut(&mut dyn AnyNode) -> bool>(node: &mut dyn AnyNode, pred: &mut F) -> usize {
+    let mut count = usize::from(pred(node));
+    for ch in node.children() {
+        count += count_nodes(ch, pred);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::*;
+
+    #[derive(Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Increment,
+        Later,
+        Alert,
+        Quit,
+    }
+
+    fn update(c: &mut Counter, msg: Message) -> Task<Message> {
+        match msg {
+            Message::Increment => c.value += 1,
+            Message::Later => return Task::perform(|| Message::Increment),
+            Message::Alert => return Task::alert("alert"),
+            Message::Quit => return Task::exit(),
+        }
+        Task::none()
+    }
+
+    fn view(c: &Counter) -> View<Message> {
+        Column::new(&[
+            Button::new("+", Message::Increment).view(),
+            Frame::new(&c.value.to_string()).view(),
+            Frame::<()>::new("mapped")
+                .key("mapped")
+                .view()
+                .map(|_| Message::Increment),
+        ])
+        .view()
+    }
+
+    #[test]
+    fn send_updates_state_and_view() {
+        let mut app = TestApp::new(update, view);
+        app.send(Message::Increment).send(Message::Increment);
+        assert_eq!(app.state().value, 2);
+        assert!(app.find_by_label("2").is_some());
+        assert!(app.find_by_label("0").is_none());
+    }
+
+    #[test]
+    fn history_holds_task_messages() {
+        let mut app = TestApp::new(update, view);
+        app.send(Message::Later);
+        assert_eq!(app.history(), [Message::Later, Message::Increment]);
+        assert_eq!(app.state().value, 1);
+    }
+
+    #[test]
+    fn find_searches_map_subtrees() {
+        let mut app = TestApp::new(update, view);
+        let node = app.find_by_key("mapped").unwrap();
+        assert_eq!(node.wprops().label.as_deref(), Some("mapped"));
+        assert_eq!(app.count(|n| *n.typ() == VNodeType::Frame), 2);
+    }
+
+    #[test]
+    fn exit_and_main_thread_tasks() {
+        let mut app = TestApp::new(update, view);
+        app.send(Message::Alert);
+        assert_eq!(app.main_thread_tasks(), 1);
+        assert_eq!(app.history(), [Message::Alert]);
+        assert!(!app.exited());
+        app.send(Message::Quit);
+        assert!(app.exited());
+    }
+}
@@ -1 +1,116 @@
 pub use fltk_theme::*;
+
+use crate::settings::Settings;
+use fltk::{app, enums};
+
+/// The look of the application: colours, scheme, widget theme and fonts.
+///
+/// It is taken from the `Settings` at startup, and can be switched at runtime
+/// with `Application::theme` or `Task::set_theme`. Colours, scheme and widget theme
+/// change at once, but `font_size` is only a default read when a widget is created,
+/// so switching it at runtime leaves the existing widgets at their size.
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub color_map: &'static [ColorMap],
+    pub scheme: app::Scheme,
+    pub theme: Option<ThemeType>,
+    pub font: Option<enums::Font>,
+    /// The default size of labels and text, used by the widgets created afterwards.
+    pub font_size: u8,
+    pub menu_linespacing: i32,
+    pub background: Option<enums::Color>,
+    pub foreground: Option<enums::Color>,
+    pub background2: Option<enums::Color>,
+    pub inactive: Option<enums::Color>,
+    pub selection: Option<enums::Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from(&Settings::<()>::default())
+    }
+}
+
+impl<Message> From<&Settings<Message>> for Theme {
+    fn from(settings: &Settings<Message>) -> Self {
+        Self {
+            color_map: settings.color_map,
+            scheme: settings.scheme,
+            theme: settings.theme,
+            font: settings.font,
+            font_size: settings.font_size,
+            menu_linespacing: settings.menu_linespacing,
+            background: settings.background,
+            foreground: settings.foreground,
+            background2: settings.background2,
+            inactive: settings.inactive,
+            selection: settings.selection,
+        }
+    }
+}
+
+// Colour maps and theme types don't implement PartialEq
+impl PartialEq for Theme {
+    fn eq(&self, other: &Self) -> bool {
+        let map = |m: &[ColorMap]| {
+            m.iter()
+                .map(|c| (c.index, c.r, c.g, c.b))
+                .collect::<Vec<_>>()
+        };
+        map(self.color_map) == map(other.color_map)
+            && self.scheme == other.scheme
+            && self.theme.map(|t| t as i32) == other.theme.map(|t| t as i32)
+            && self.font == other.font
+            && self.font_size == other.font_size
+            && self.menu_linespacing == other.menu_linespacing
+            && self.background == other.background
+            && self.foreground == other.foreground
+            && self.background2 == other.background2
+            && self.inactive == other.inactive
+            && self.selection == other.selection
+    }
+}
+
+impl Theme {
+    /// Applies the theme to the whole application. Windows have to be redrawn for
+    /// the change to show.
+    pub(crate) fn apply(&self) {
+        // Start from the default colours, so that no colour of a previous theme is left
+        reset_color_map();
+        ColorTheme::from_colormap(self.color_map).apply();
+
+        if let Some(color) = self.background {
+            let c = color.to_rgb();
+            app::background(c.0, c.1, c.2);
+        }
+        if let Some(color) = self.background2 {
+            let c = color.to_rgb();
+            app::background2(c.0, c.1, c.2);
+        }
+        if let Some(color) = self.foreground {
+            let c = color.to_rgb();
+            app::foreground(c.0, c.1, c.2);
+        }
+        if let Some(color) = self.inactive {
+            let c = color.to_rgb();
+            app::set_inactive_color(c.0, c.1, c.2);
+        }
+        if let Some(color) = self.selection {
+            let c = color.to_rgb();
+            app::set_selection_color(c.0, c.1, c.2);
+        }
+        app::set_font_size(self.font_size);
+
+        if let Some(theme) = self.theme {
+            WidgetTheme::new(theme).apply();
+        }
+
+        app::set_scheme(self.scheme);
+
+        if let Some(font) = self.font {
+            app::set_font(font);
+        }
+
+        app::set_menu_linespacing(self.menu_linespacing);
+    }
+}
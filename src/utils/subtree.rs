@@ -1,6 +1,7 @@
 use crate::vdom::VirtualDom;
 use crate::vnode::{VNode, View};
 use fltk::{prelude::*, widget};
+use std::collections::HashMap;
 
 pub fn replace_subtree<Message>(
     old: &mut View<Message>,
@@ -9,26 +10,91 @@ pub fn replace_subtree<Message>(
 ) where
     Message: Clone + 'static + Send + Sync,
 {
-    remove_subtree(old, dom);
+    old.unmount(dom);
     new.mount(dom);
 }
 
-pub fn remove_subtree<Message>(old: &mut View<Message>, dom: &VirtualDom<Message>)
+pub fn remove_subtree<Message, V>(old: &mut V, dom: &VirtualDom<Message>)
 where
     Message: Clone + 'static + Send + Sync,
+    V: VNode<Message> + ?Sized,
 {
     if let Some(gprops) = old.gprops() {
         for ch in &mut gprops.children {
-            remove_subtree(ch, dom);
+            ch.unmount(dom);
         }
     }
     let old_ptr = old.node_id();
     // Clean up any subscriptions owned by this node
     dom.unsubscribe_owner(old_ptr);
-    if let Some(wu) = dom.widget_map.borrow_mut().remove(&old_ptr) {
+    let wu = dom.widget_map.borrow_mut().remove(&old_ptr);
+    if let Some(wu) = wu {
         if let Some(mut par) = wu.view().parent() {
             par.remove(&wu.view());
-            widget::Widget::delete(wu.view());
+        }
+        // top-level windows have no parent but still need to go away
+        widget::Widget::delete(wu.view());
+    }
+}
+
+/// Children are reconciled by key as soon as one of them carries a key.
+pub fn is_keyed<Message: 'static>(old: &mut [View<Message>], new: &mut [View<Message>]) -> bool {
+    old.iter_mut()
+        .chain(new.iter_mut())
+        .any(|ch| ch.wprops().key.is_some())
+}
+
+/// Matches children by key (unkeyed ones in order), patching and moving the existing
+/// widgets so that their state survives, mounting new children and removing stale ones.
+pub fn patch_keyed_children<Message, G>(
+    grp: &mut G,
+    old: &mut [View<Message>],
+    new: &mut [View<Message>],
+    dom: &VirtualDom<Message>,
+) where
+    Message: Clone + 'static + Send + Sync,
+    G: GroupExt,
+{
+    let mut keyed: HashMap<String, usize> = HashMap::new();
+    let mut unkeyed = std::collections::VecDeque::new();
+    for (i, ch) in old.iter_mut().enumerate() {
+        if let Some(key) = ch.wprops().key.clone() {
+            keyed.entry(key).or_insert(i);
+        } else {
+            unkeyed.push_back(i);
+        }
+    }
+    let mut used = vec![false; old.len()];
+
+    // Anything mounted while reconciling (new children or replaced subtrees) lands in grp
+    grp.begin();
+    for (i, ch) in new.iter_mut().enumerate() {
+        let matched = match ch.wprops().key.clone() {
+            Some(key) => keyed.remove(&key),
+            None => unkeyed.pop_front(),
+        };
+        if let Some(j) = matched {
+            used[j] = true;
+            ch.patch(&mut old[j], dom);
+        } else {
+            ch.mount(dom);
+        }
+        let w = dom
+            .widget_map
+            .borrow()
+            .get(&ch.node_id())
+            .map(|wu| wu.view());
+        if let Some(w) = w {
+            if grp.find(&w) != i as i32 {
+                grp.insert(&w, i as i32);
+            }
+        }
+    }
+    grp.end();
+
+    for (j, ch) in old.iter_mut().enumerate() {
+        if !used[j] {
+            ch.unmount(dom);
         }
     }
 }
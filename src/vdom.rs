@@ -1,15 +1,37 @@
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::{cell::RefCell, rc::Rc};
 
-use crate::vnode::View;
-use crate::widgets::WidgetMap;
+use crate::task::ViewOp;
+use crate::vnode::{AnyNode, VNodeType, View};
+use crate::widgets::{WidgetMap, WidgetUnion};
+
+type Subscribers = Rc<RefCell<Vec<(usize, Rc<dyn Fn(&dyn Any)>)>>>;
+
+/// Delivers the messages emitted by widget callbacks to the application.
+pub struct Emitter<Message> {
+    f: Rc<dyn Fn(Message)>,
+}
+
+impl<Message> Clone for Emitter<Message> {
+    fn clone(&self) -> Self {
+        Self { f: self.f.clone() }
+    }
+}
+
+impl<Message> Emitter<Message> {
+    pub fn emit(&self, message: Message) {
+        (self.f)(message)
+    }
+}
 
 #[derive(Clone)]
 pub struct VirtualDom<Message> {
-    pub root: Rc<RefCell<View<Message>>>,
+    pub root: Rc<RefCell<Option<View<Message>>>>,
     pub widget_map: Rc<RefCell<WidgetMap>>,
-    #[allow(clippy::type_complexity)]
-    subscribers: Rc<RefCell<Vec<(usize, Rc<dyn Fn(&Message)>)>>>,
+    subscribers: Subscribers,
+    emitter: Emitter<Message>,
+    app_message: TypeId,
 }
 
 impl<Message> VirtualDom<Message>
@@ -17,26 +39,65 @@ where
     Message: Clone + 'static + Send + Sync,
 {
     pub(crate) fn new(root: View<Message>) -> Self {
-        let root_rc = Rc::new(RefCell::new(root));
+        let root_rc = Rc::new(RefCell::new(None));
         let widget_map = Rc::new(RefCell::new(HashMap::new()));
 
         let dom = Self {
             root: root_rc.clone(),
             widget_map: widget_map.clone(),
             subscribers: Rc::new(RefCell::new(Vec::new())),
+            emitter: Emitter {
+                f: Rc::new(|msg| fltk::app::Sender::<Message>::get().send(msg)),
+            },
+            app_message: TypeId::of::<Message>(),
         };
 
-        {
-            root_rc.borrow().mount(&dom);
-        }
+        root.mount(&dom);
+        *root_rc.borrow_mut() = Some(root);
 
         dom
     }
 
+    /// Creates a dom sharing the widgets of this one, whose messages are mapped back to
+    /// this dom's message type before being emitted.
+    pub(crate) fn map<M>(&self, mapper: Rc<dyn Fn(M) -> Message>) -> VirtualDom<M>
+    where
+        M: Clone + 'static + Send + Sync,
+    {
+        let parent = self.emitter.clone();
+        let subscribers = self.subscribers.clone();
+        let app_message = self.app_message;
+        VirtualDom {
+            root: Rc::new(RefCell::new(None)),
+            widget_map: self.widget_map.clone(),
+            subscribers: self.subscribers.clone(),
+            emitter: Emitter {
+                f: Rc::new(move |msg: M| {
+                    // The application only dispatches its own message type,
+                    // so subscribers of a mapped subtree are notified on emission
+                    if TypeId::of::<M>() != app_message {
+                        dispatch_to(&subscribers, &msg);
+                    }
+                    parent.emit(mapper(msg));
+                }),
+            },
+            app_message,
+        }
+    }
+
+    pub fn emitter(&self) -> Emitter<Message> {
+        self.emitter.clone()
+    }
+
     pub(crate) fn subscribe_owned<F: 'static + Fn(&Message)>(&self, owner: usize, callback: F) {
-        self.subscribers
-            .borrow_mut()
-            .push((owner, Rc::new(callback)));
+        self.subscribers.borrow_mut().push((
+            owner,
+            Rc::new(move |msg: &dyn Any| {
+                if let Some(msg) = msg.downcast_ref::<Message>() {
+                    callback(msg);
+                }
+            }),
+        ));
     }
 
     pub(crate) fn unsubscribe_owner(&self, owner: usize) {
@@ -44,15 +105,75 @@ where
     }
 
     pub(crate) fn dispatch(&self, message: Message) {
-        for (_, subscriber) in self.subscribers.borrow().iter() {
-            subscriber(&message);
-        }
+        dispatch_to(&self.subscribers, &message);
     }
 
     pub(crate) fn patch(&self, new: View<Message>) {
         let mut new = new;
-        let mut old = self.root.borrow_mut();
-        new.patch(&mut old, self);
-        *old = new;
+        let mut root = self.root.borrow_mut();
+        if let Some(old) = root.as_mut() {
+            new.patch(old, self);
+        }
+        *root = Some(new);
+    }
+
+    pub(crate) fn run_view_op(&self, op: &ViewOp) {
+        match op {
+            ViewOp::ScrollToNode(key) => self.scroll_to_node(key),
+        }
+    }
+
+    /// Scrolls the `Scroll` nearest to the node with the key so that the node is fully
+    /// visible. Keys only have to be unique within their `Scroll`, the first node found
+    /// inside one is used.
+    fn scroll_to_node(&self, key: &str) {
+        let found = match self.root.borrow_mut().as_mut() {
+            Some(root) => find_in_scroll(root, None, key),
+            None => None,
+        };
+        let Some((scroll, node)) = found else {
+            return;
+        };
+        let widgets = {
+            let map = self.widget_map.borrow();
+            match (map.get(&scroll), map.get(&node)) {
+                (Some(WidgetUnion::Scroll(s)), Some(w)) => Some((s.clone(), w.view())),
+                _ => None,
+            }
+        };
+        if let Some((mut s, w)) = widgets {
+            crate::view::scroll_into_view(&mut s, &w);
+        }
+    }
+}
+
+/// Finds the node with the key inside a `Scroll`, returning its id and the id of the
+/// nearest `Scroll` around it.
+fn find_in_scroll(
+    node: &mut dyn AnyNode,
+    scroll: Option<usize>,
+    key: &str,
+) -> Option<(usize, usize)> {
+    if let Some(scroll) = scroll.filter(|_| node.wprops().key.as_deref() == Some(key)) {
+        return Some((scroll, node.node_id()));
+    }
+    let scroll = match node.typ() {
+        VNodeType::Scroll => Some(node.node_id()),
+        _ => scroll,
+    };
+    node.children()
+        .into_iter()
+        .find_map(|child| find_in_scroll(child, scroll, key))
+}
+
+fn dispatch_to<Message: 'static>(subscribers: &Subscribers, message: &Message) {
+    // Clone the list so that a subscriber may (un)subscribe while being notified
+    let subscribers: Vec<_> = subscribers
+        .borrow()
+        .iter()
+        .map(|(_, s)| s.clone())
+        .collect();
+    for subscriber in subscribers {
+        subscriber(message);
     }
 }
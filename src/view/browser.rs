@@ -1,38 +1,162 @@
+use crate::image::{Image, IMAGES};
 use crate::props::*;
 use crate::utils::macros::*;
-use crate::vdom::VirtualDom;
+use crate::vdom::{Emitter, VirtualDom};
 use crate::vnode::{VNode, VNodeType, View};
 use crate::widgets::WidgetUnion;
-use fltk::{prelude::*, *};
+use fltk::{enums::*, prelude::*, *};
 use std::rc::Rc;
 
+/// The selection of a browser: a line for single-selection browsers, all the selected lines
+/// for a `MultiBrowser`. Lines start at 1.
+trait Selection: Clone + PartialEq + 'static {
+    fn apply<B: BrowserExt>(&self, b: &mut B);
+    fn read<B: BrowserExt>(b: &B) -> Self;
+}
+
+impl Selection for i32 {
+    fn apply<B: BrowserExt>(&self, b: &mut B) {
+        b.select(*self);
+    }
+    fn read<B: BrowserExt>(b: &B) -> Self {
+        b.value()
+    }
+}
+
+impl Selection for Vec<i32> {
+    fn apply<B: BrowserExt>(&self, b: &mut B) {
+        for line in 1..=b.size() {
+            if self.contains(&line) {
+                b.select(line);
+            } else {
+                b.deselect(line);
+            }
+        }
+    }
+    fn read<B: BrowserExt>(b: &B) -> Self {
+        b.selected_items()
+    }
+}
+
+#[derive(Clone)]
+struct BrowserCallbacks<S, Message> {
+    #[allow(clippy::type_complexity)]
+    on_change: Option<Rc<Box<dyn Fn(S) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_double_click: Option<Rc<Box<dyn Fn(i32) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_select_keys: Option<Rc<Box<dyn Fn(Vec<String>) -> Message>>>,
+    keys: Vec<String>,
+}
+
+fn set_browser_callback<B, S, Message>(
+    b: &mut B,
+    when: Option<CallbackTrigger>,
+    cbs: BrowserCallbacks<S, Message>,
+    sender: Emitter<Message>,
+) where
+    B: BrowserExt + WidgetBase,
+    S: Selection,
+    Message: 'static,
+{
+    if cbs.on_double_click.is_some() {
+        // A second click on the selected line doesn't change the selection
+        b.set_trigger(CallbackTrigger::ReleaseAlways);
+    } else {
+        b.set_trigger(when.unwrap_or(CallbackTrigger::Release));
+    }
+    b.set_callback(move |b| {
+        let clicked = matches!(app::event(), Event::Push | Event::Released);
+        if clicked && app::event_clicks() {
+            if let Some(cb) = &cbs.on_double_click {
+                sender.emit(cb(b.value()));
+            }
+            return;
+        }
+        if let Some(cb) = &cbs.on_change {
+            sender.emit(cb(S::read(b)));
+        }
+        if let Some(cb) = &cbs.on_select_keys {
+            let keys = b
+                .selected_items()
+                .into_iter()
+                .filter_map(|line| cbs.keys.get(line as usize - 1).cloned())
+                .collect();
+            sender.emit(cb(keys));
+        }
+    });
+}
+
 macro_rules! define_browser {
-    ($name: ident) => {
+    ($name: ident, $sel: ty) => {
         #[derive(Clone)]
         pub struct $name<Message> {
             node_id: usize,
             typ: VNodeType,
             wprops: WidgetProps,
             items: Vec<String>,
-            selection: i32,
-            #[allow(clippy::type_complexity)]
-            on_change: Option<Rc<Box<dyn Fn(i32) -> Message>>>,
+            icons: Vec<(i32, Image)>,
+            selection: $sel,
+            column_widths: Vec<i32>,
+            column_char: Option<char>,
+            format_char: Option<char>,
+            callbacks: BrowserCallbacks<$sel, Message>,
         }
 
         impl<Message: Clone> $name<Message> {
-            pub fn new(items: &[&str], selection: i32) -> Self {
+            pub fn new(items: &[&str], selection: $sel) -> Self {
                 let items = items.iter().map(|s| s.to_string()).collect();
                 Self {
                     node_id: 0,
                     typ: VNodeType::$name,
                     wprops: WidgetProps::default(),
                     items,
+                    icons: vec![],
                     selection,
-                    on_change: None,
+                    column_widths: vec![],
+                    column_char: None,
+                    format_char: None,
+                    callbacks: BrowserCallbacks {
+                        on_change: None,
+                        on_double_click: None,
+                        on_select_keys: None,
+                        keys: vec![],
+                    },
                 }
             }
-            pub fn on_change<F: 'static + Fn(i32) -> Message>(mut self, f: F) -> Self {
-                self.on_change = Some(Rc::new(Box::new(f)));
+            pub fn on_change<F: 'static + Fn($sel) -> Message>(mut self, f: F) -> Self {
+                self.callbacks.on_change = Some(Rc::new(Box::new(f)));
+                self
+            }
+            /// Called with the line which was double-clicked, instead of `on_change`.
+            pub fn on_double_click<F: 'static + Fn(i32) -> Message>(mut self, f: F) -> Self {
+                self.callbacks.on_double_click = Some(Rc::new(Box::new(f)));
+                self
+            }
+            /// Called with the keys of the selected lines, see `keys`.
+            pub fn on_select_keys<F: 'static + Fn(Vec<String>) -> Message>(mut self, f: F) -> Self {
+                self.callbacks.on_select_keys = Some(Rc::new(Box::new(f)));
+                self
+            }
+            /// Associates a key to each line, in the order of the items.
+            pub fn keys(mut self, keys: &[&str]) -> Self {
+                self.callbacks.keys = keys.iter().map(|s| s.to_string()).collect();
+                self
+            }
+            pub fn icon(mut self, line: i32, image: Image) -> Self {
+                self.icons.push((line, image));
+                self
+            }
+            pub fn column_widths(mut self, widths: &[i32]) -> Self {
+                self.column_widths = widths.to_vec();
+                self
+            }
+            pub fn column_char(mut self, c: char) -> Self {
+                self.column_char = Some(c);
+                self
+            }
+            pub fn format_char(mut self, c: char) -> Self {
+                self.format_char = Some(c);
                 self
             }
         }
@@ -45,51 +169,79 @@ macro_rules! define_browser {
             fn mount(&self, dom: &VirtualDom<Message>) {
                 let mut b = browser::$name::default();
                 default_mount!(b, self, dom, $name, {
-                    let on_change = self.on_change.clone();
-                    for item in &self.items {
-                        b.add(item);
+                    if let Some(c) = self.column_char {
+                        b.set_column_char(c);
                     }
-                    b.select(self.selection);
-                    b.set_callback(move |b| {
-                        let v = b.value();
-                        if let Some(on_change) = &on_change {
-                            app::Sender::<Message>::get().send(on_change(v));
-                        }
-                    });
+                    if let Some(c) = self.format_char {
+                        b.set_format_char(c);
+                    }
+                    if !self.column_widths.is_empty() {
+                        b.set_column_widths(&self.column_widths);
+                    }
+                    add_lines(&mut b, &self.items, &self.icons);
+                    self.selection.apply(&mut b);
+                    set_browser_callback(
+                        &mut b,
+                        self.wprops.when,
+                        self.callbacks.clone(),
+                        dom.emitter(),
+                    );
                 });
             }
             fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
                 let b;
                 default_patch!(b, self, old, dom, $name, {
                     let old: &$name<Message> = old.as_any().downcast_ref().unwrap();
-                    if self.items != old.items {
+                    if self.column_char != old.column_char {
+                        b.set_column_char(self.column_char.unwrap_or('\t'));
+                    }
+                    if self.format_char != old.format_char {
+                        b.set_format_char(self.format_char.unwrap_or('@'));
+                    }
+                    if self.column_widths != old.column_widths {
+                        b.set_column_widths(&self.column_widths);
+                    }
+                    if self.items != old.items || self.icons != old.icons {
                         b.clear();
-                        for item in &self.items {
-                            b.add(item);
-                        }
-                        b.select(self.selection);
+                        add_lines(b, &self.items, &self.icons);
+                        self.selection.apply(b);
                     }
                     if self.selection != old.selection {
-                        b.select(self.selection);
+                        self.selection.apply(b);
                     }
-                    let on_change = self.on_change.clone();
-                    b.set_callback(move |b| {
-                        let v = b.value();
-                        if let Some(on_change) = &on_change {
-                            app::Sender::<Message>::get().send(on_change(v));
-                        }
-                    });
+                    set_browser_callback(
+                        b,
+                        self.wprops.when,
+                        self.callbacks.clone(),
+                        dom.emitter(),
+                    );
                 });
             }
         }
     };
 }
 
-define_browser!(Browser);
-define_browser!(SelectBrowser);
-define_browser!(HoldBrowser);
-define_browser!(MultiBrowser);
-define_browser!(FileBrowser);
+fn add_lines<B: BrowserExt>(b: &mut B, items: &[String], icons: &[(i32, Image)]) {
+    for item in items {
+        b.add(item);
+    }
+    for (line, i) in icons {
+        unsafe {
+            b.set_icon(
+                *line,
+                Some(fltk::image::Image::from_image_ptr(
+                    IMAGES.lock().unwrap().get(&i.idx).unwrap().as_image_ptr(),
+                )),
+            );
+        }
+    }
+}
+
+define_browser!(Browser, i32);
+define_browser!(SelectBrowser, i32);
+define_browser!(HoldBrowser, i32);
+define_browser!(MultiBrowser, Vec<i32>);
+define_browser!(FileBrowser, i32);
 
 #[derive(Clone)]
 pub struct CheckBrowser<Message> {
@@ -129,6 +281,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for CheckBrowser<Mes
                 b.add(item, *checked);
             }
             let on_change = self.on_change.clone();
+            let sender = dom.emitter();
             b.set_callback(move |b| {
                 if let Some(on_change) = &on_change {
                     let mut checked: Vec<i32> = Vec::new();
@@ -138,7 +291,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for CheckBrowser<Mes
                             checked.push(ii);
                         }
                     }
-                    app::Sender::<Message>::get().send(on_change(checked));
+                    sender.emit(on_change(checked));
                 }
             });
         });
@@ -155,6 +308,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for CheckBrowser<Mes
             }
             if self.on_change.is_some() != old.on_change.is_some() {
                 let on_change = self.on_change.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |b| {
                     if let Some(on_change) = &on_change {
                         let mut checked: Vec<i32> = Vec::new();
@@ -164,7 +318,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for CheckBrowser<Mes
                                 checked.push(ii);
                             }
                         }
-                        app::Sender::<Message>::get().send(on_change(checked));
+                        sender.emit(on_change(checked));
                     }
                 });
             }
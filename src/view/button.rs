@@ -76,8 +76,9 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Button<Message>
     }
     fn mount(&self, dom: &VirtualDom<Message>) {
         let mut b = button::Button::default();
-        let sender: app::Sender<Message> = app::Sender::get();
-        b.emit(sender, self.callback.clone());
+        let sender = dom.emitter();
+        let msg = self.callback.clone();
+        b.set_callback(move |_| sender.emit(msg.clone()));
         default_mount!(b, self, dom, Button, {
             set_bprops(&mut b, &self.bprops);
         });
@@ -86,8 +87,9 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Button<Message>
         let b;
         default_patch!(b, self, old, dom, Button, {
             let old: &Button<Message> = old.as_any().downcast_ref().unwrap();
-            let sender: app::Sender<Message> = app::Sender::get();
-            b.emit(sender, self.callback.clone());
+            let sender = dom.emitter();
+            let msg = self.callback.clone();
+            b.set_callback(move |_| sender.emit(msg.clone()));
             update_bprops(b, &old.bprops, &self.bprops);
         });
     }
@@ -143,10 +145,11 @@ macro_rules! define_button {
                     set_bprops(&mut b, &self.bprops);
                     b.set_value(self.bprops.value);
                     let on_change = self.on_change.clone();
+                    let sender = dom.emitter();
                     b.set_callback(move |b| {
                         let v = b.value();
                         if let Some(on_change) = &on_change {
-                            app::Sender::<Message>::get().send(on_change(v));
+                            sender.emit(on_change(v));
                         }
                     });
                 });
@@ -161,10 +164,11 @@ macro_rules! define_button {
                     if oldi.value != newi.value {
                         b.set_value(newi.value);
                         let on_change = self.on_change.clone();
+                        let sender = dom.emitter();
                         b.set_callback(move |b| {
                             let v = b.value();
                             if let Some(on_change) = &on_change {
-                                app::Sender::<Message>::get().send(on_change(v));
+                                sender.emit(on_change(v));
                             }
                         });
                     }
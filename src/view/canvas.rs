@@ -0,0 +1,295 @@
+use crate::image::{Image, IMAGES};
+use crate::props::*;
+use crate::utils::macros::*;
+use crate::vdom::VirtualDom;
+use crate::vnode::{VNode, VNodeType, View};
+use crate::widgets::{IsWidget, WidgetUnion};
+use fltk::enums::{Color, Event, Font};
+use fltk::{app, draw, frame, prelude::*};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub use fltk::draw::LineStyle;
+
+/// A drawing command of a `Canvas`. Coordinates are relative to the canvas,
+/// and colour, font and line style apply to the commands which follow them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCmd {
+    Color(Color),
+    Font(Font, i32),
+    LineStyle(LineStyle, i32),
+    Line {
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+    },
+    Rect {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        filled: bool,
+    },
+    /// An arc, or a pie when filled, of the ellipse bounded by the rectangle.
+    /// Angles are in degrees, counter-clockwise from 3 o'clock.
+    Arc {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        start: f64,
+        end: f64,
+        filled: bool,
+    },
+    Polygon {
+        points: Vec<(i32, i32)>,
+        filled: bool,
+    },
+    /// Text drawn with its baseline at `y`.
+    Text {
+        text: String,
+        x: i32,
+        y: i32,
+    },
+    Image {
+        image: Image,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    },
+    /// Restricts the following commands to a rectangle, until the matching `PopClip`.
+    PushClip {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    },
+    PopClip,
+}
+
+type MouseCallback<Message> = Option<Rc<Box<dyn Fn(i32, i32) -> Message>>>;
+
+struct CanvasState<Message> {
+    cmds: Vec<DrawCmd>,
+    on_press: MouseCallback<Message>,
+    on_release: MouseCallback<Message>,
+    on_drag: MouseCallback<Message>,
+    on_move: MouseCallback<Message>,
+}
+
+#[derive(Clone)]
+struct CanvasWidget<Message> {
+    f: frame::Frame,
+    state: Rc<RefCell<CanvasState<Message>>>,
+}
+
+impl<Message: 'static> IsWidget for CanvasWidget<Message> {
+    fn as_widget(&self) -> fltk::widget::Widget {
+        unsafe { self.f.into_widget() }
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// A widget drawing a list of commands, redrawn when the list changes.
+#[derive(Clone)]
+pub struct Canvas<Message> {
+    node_id: usize,
+    typ: VNodeType,
+    wprops: WidgetProps,
+    cmds: Vec<DrawCmd>,
+    on_press: MouseCallback<Message>,
+    on_release: MouseCallback<Message>,
+    on_drag: MouseCallback<Message>,
+    on_move: MouseCallback<Message>,
+}
+
+impl<Message: Clone + 'static> Canvas<Message> {
+    pub fn new(cmds: Vec<DrawCmd>) -> Self {
+        Self {
+            node_id: 0,
+            typ: VNodeType::Other(std::any::TypeId::of::<CanvasWidget<Message>>()),
+            wprops: WidgetProps::default(),
+            cmds,
+            on_press: None,
+            on_release: None,
+            on_drag: None,
+            on_move: None,
+        }
+    }
+    pub fn on_press<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_press = Some(Rc::new(Box::new(f)));
+        self
+    }
+    pub fn on_release<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_release = Some(Rc::new(Box::new(f)));
+        self
+    }
+    pub fn on_drag<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_drag = Some(Rc::new(Box::new(f)));
+        self
+    }
+    pub fn on_move<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_move = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    fn update_callbacks(&self, st: &mut CanvasState<Message>) {
+        st.on_press = self.on_press.clone();
+        st.on_release = self.on_release.clone();
+        st.on_drag = self.on_drag.clone();
+        st.on_move = self.on_move.clone();
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Canvas<Message> {
+    default_impl!();
+    fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
+        None
+    }
+    fn mount(&self, dom: &VirtualDom<Message>) {
+        let mut f = frame::Frame::default();
+        set_wprops(&mut f, &self.wprops);
+        let state = Rc::new(RefCell::new(CanvasState {
+            cmds: self.cmds.clone(),
+            on_press: None,
+            on_release: None,
+            on_drag: None,
+            on_move: None,
+        }));
+        self.update_callbacks(&mut state.borrow_mut());
+        f.draw({
+            let state = state.clone();
+            move |f| {
+                draw::push_clip(f.x(), f.y(), f.w(), f.h());
+                draw_cmds(&state.borrow().cmds, f.x(), f.y());
+                draw::pop_clip();
+            }
+        });
+        f.handle({
+            let state = state.clone();
+            let sender = dom.emitter();
+            move |f, ev| {
+                let st = state.borrow();
+                // Claiming these events is what makes the canvas receive drags and moves
+                let cb = match ev {
+                    Event::Push => &st.on_press,
+                    Event::Released => &st.on_release,
+                    Event::Drag => &st.on_drag,
+                    Event::Move => &st.on_move,
+                    Event::Enter => return true,
+                    _ => return false,
+                };
+                if let Some(cb) = cb {
+                    let msg = cb(app::event_x() - f.x(), app::event_y() - f.y());
+                    drop(st);
+                    sender.emit(msg);
+                }
+                true
+            }
+        });
+        dom.widget_map.borrow_mut().insert(
+            self.node_id,
+            WidgetUnion::Other(Rc::new(CanvasWidget { f, state })),
+        );
+    }
+    fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
+        if self.typ != *old.typ() {
+            crate::utils::subtree::replace_subtree(old, self, dom);
+            return;
+        }
+        self.set_node_id(old.node_id());
+        let mut map = dom.widget_map.borrow_mut();
+        if let Some(WidgetUnion::Other(ref mut w)) = map.get_mut(&old.node_id()) {
+            update_wprops(&mut w.as_widget(), old.wprops(), &self.wprops);
+            let old: &Canvas<Message> = old.as_any().downcast_ref().unwrap();
+            let mut w = w
+                .as_any()
+                .downcast_ref::<CanvasWidget<Message>>()
+                .unwrap()
+                .clone();
+            let mut st = w.state.borrow_mut();
+            self.update_callbacks(&mut st);
+            if self.cmds != old.cmds {
+                st.cmds.clone_from(&self.cmds);
+                w.f.redraw();
+            }
+        }
+    }
+}
+
+fn draw_cmds(cmds: &[DrawCmd], ox: i32, oy: i32) {
+    let mut clips = 0;
+    for cmd in cmds {
+        match cmd {
+            DrawCmd::Color(c) => draw::set_draw_color(*c),
+            DrawCmd::Font(font, size) => draw::set_font(*font, *size),
+            DrawCmd::LineStyle(style, width) => draw::set_line_style(*style, *width),
+            DrawCmd::Line { x1, y1, x2, y2 } => draw::draw_line(ox + x1, oy + y1, ox + x2, oy + y2),
+            DrawCmd::Rect { x, y, w, h, filled } => {
+                if *filled {
+                    draw::draw_rectf(ox + x, oy + y, *w, *h);
+                } else {
+                    draw::draw_rect(ox + x, oy + y, *w, *h);
+                }
+            }
+            DrawCmd::Arc {
+                x,
+                y,
+                w,
+                h,
+                start,
+                end,
+                filled,
+            } => {
+                if *filled {
+                    draw::draw_pie(ox + x, oy + y, *w, *h, *start, *end);
+                } else {
+                    draw::draw_arc(ox + x, oy + y, *w, *h, *start, *end);
+                }
+            }
+            DrawCmd::Polygon { points, filled } => {
+                if *filled {
+                    draw::begin_complex_polygon();
+                } else {
+                    draw::begin_loop();
+                }
+                for (x, y) in points {
+                    draw::vertex((ox + x) as f64, (oy + y) as f64);
+                }
+                if *filled {
+                    draw::end_complex_polygon();
+                } else {
+                    draw::end_loop();
+                }
+            }
+            DrawCmd::Text { text, x, y } => draw::draw_text(text, ox + x, oy + y),
+            DrawCmd::Image { image, x, y, w, h } => {
+                if let Some(img) = IMAGES.lock().unwrap().get_mut(&image.idx) {
+                    img.draw(ox + x, oy + y, *w, *h);
+                }
+            }
+            DrawCmd::PushClip { x, y, w, h } => {
+                draw::push_clip(ox + x, oy + y, *w, *h);
+                clips += 1;
+            }
+            DrawCmd::PopClip => {
+                if clips > 0 {
+                    draw::pop_clip();
+                    clips -= 1;
+                }
+            }
+        }
+    }
+    for _ in 0..clips {
+        draw::pop_clip();
+    }
+    // Don't leak the line style to the widgets drawn next
+    draw::set_line_style(LineStyle::Solid, 0);
+}
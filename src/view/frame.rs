@@ -42,3 +42,45 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Frame<Message> {
         default_patch!(b, self, old, dom, Frame);
     }
 }
+
+/// Empty space in a `Column` or `Row`. It takes a share of the free space like any
+/// other child, unless sized with `fixed`, `min_size`, `max_size` or `flex`.
+#[derive(Clone)]
+pub struct Space<Message> {
+    node_id: usize,
+    typ: VNodeType,
+    wprops: WidgetProps,
+    phantom: PhantomData<Message>,
+}
+
+impl<Message> Space<Message> {
+    pub fn new() -> Self {
+        Self {
+            node_id: 0,
+            typ: VNodeType::Space,
+            wprops: WidgetProps::default(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Message> Default for Space<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Space<Message> {
+    default_impl!();
+    fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
+        None
+    }
+    fn mount(&self, dom: &VirtualDom<Message>) {
+        let mut b = frame::Frame::default();
+        default_mount!(b, self, dom, Frame);
+    }
+    fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
+        let b;
+        default_patch!(b, self, old, dom, Frame);
+    }
+}
@@ -1,9 +1,12 @@
+pub use crate::props::FlexAlign;
 use crate::props::*;
 use crate::utils::macros::*;
 use crate::vdom::VirtualDom;
 use crate::vnode::{VNode, VNodeType, View};
 use crate::widgets::WidgetUnion;
 use fltk::{prelude::*, *};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 macro_rules! update_group_children {
     ($old: tt, $new: tt, $dom: tt, $typ: ident) => {
@@ -11,35 +14,199 @@ macro_rules! update_group_children {
         let oldg = $old.gprops().unwrap();
         let mut newg_opt = $new.gprops();
         let newg = newg_opt.as_mut().unwrap();
-        let min_len = oldg.children.len().min(newg.children.len());
-        for i in 0..min_len {
-            newg.children[i].patch(&mut oldg.children[i], $dom);
+        // Clone group handle to avoid holding a borrow across child.mount calls
+        let grp_opt = {
+            let mut map = $dom.widget_map.borrow_mut();
+            if let Some(WidgetUnion::$typ(ref mut grp)) = map.get_mut(&old_id) {
+                Some(grp.clone())
+            } else {
+                None
+            }
+        };
+        if $crate::utils::subtree::is_keyed(&mut oldg.children, &mut newg.children) {
+            if let Some(mut grp) = grp_opt {
+                $crate::utils::subtree::patch_keyed_children(
+                    &mut grp,
+                    &mut oldg.children,
+                    &mut newg.children,
+                    $dom,
+                );
+                grp.fix_layout();
+            }
+        } else {
+            let min_len = oldg.children.len().min(newg.children.len());
+            for i in 0..min_len {
+                newg.children[i].patch(&mut oldg.children[i], $dom);
+            }
+            if newg.children.len() > oldg.children.len() {
+                if let Some(mut grp) = grp_opt {
+                    grp.begin();
+                    for i in oldg.children.len()..newg.children.len() {
+                        newg.children[i].mount($dom);
+                    }
+                    grp.end();
+                    grp.fix_layout();
+                }
+            }
+            if oldg.children.len() > newg.children.len() {
+                for i in newg.children.len()..oldg.children.len() {
+                    oldg.children[i].unmount($dom);
+                }
+            }
         }
-        if newg.children.len() > oldg.children.len() {
-            // Clone group handle to avoid holding a borrow across child.mount calls
-            let grp_opt = {
-                let mut map = $dom.widget_map.borrow_mut();
-                if let Some(WidgetUnion::$typ(ref mut grp)) = map.get_mut(&old_id) {
-                    Some(grp.clone())
-                } else {
-                    None
+    };
+}
+
+/// The layout props of the children of a `Column` or `Row`, in order.
+#[derive(Default, Clone, PartialEq)]
+struct FlexLayout {
+    align: FlexAlign,
+    items: Vec<FlexItem>,
+}
+
+#[derive(Default, Clone, PartialEq)]
+struct FlexItem {
+    fixed: Option<i32>,
+    min: Option<i32>,
+    max: Option<i32>,
+    weight: Option<i32>,
+    align: Option<FlexAlign>,
+    w: Option<i32>,
+    h: Option<i32>,
+}
+
+impl FlexLayout {
+    fn new<Message: 'static>(children: &mut [View<Message>]) -> Self {
+        let items = children
+            .iter_mut()
+            .map(|c| {
+                let wprops = c.wprops();
+                FlexItem {
+                    fixed: wprops.fixed.filter(|sz| *sz > 0),
+                    min: wprops.min_size,
+                    max: wprops.max_size,
+                    weight: wprops.flex,
+                    align: wprops.align_self,
+                    w: wprops.w,
+                    h: wprops.h,
                 }
-            };
-            if let Some(mut grp) = grp_opt {
-                grp.begin();
-                for i in oldg.children.len()..newg.children.len() {
-                    newg.children[i].mount($dom);
+            })
+            .collect();
+        Self {
+            align: FlexAlign::default(),
+            items,
+        }
+    }
+}
+
+/// Splits `space` between the items along the main axis. Fixed items keep their size,
+/// the others share what is left by weight, clamped to their min and max sizes.
+fn flex_sizes(space: i32, items: &[FlexItem]) -> Vec<i32> {
+    let mut sizes: Vec<Option<i32>> = items.iter().map(|it| it.fixed).collect();
+    loop {
+        let free: Vec<usize> = (0..items.len()).filter(|i| sizes[*i].is_none()).collect();
+        if free.is_empty() {
+            break;
+        }
+        let used: i32 = sizes.iter().flatten().sum();
+        let remaining = (space - used).max(0);
+        let weight = |i: usize| items[i].weight.unwrap_or(1).max(0);
+        let total: i32 = free.iter().map(|i| weight(*i)).sum();
+        let mut shares: Vec<i32> = free
+            .iter()
+            .map(|i| {
+                if total > 0 {
+                    remaining * weight(*i) / total
+                } else {
+                    0
                 }
-                grp.end();
-                grp.fix_layout();
+            })
+            .collect();
+        // Hand out the rounding leftovers a pixel at a time, like Fl_Flex
+        let mut left = remaining - shares.iter().sum::<i32>();
+        for (i, share) in free.iter().zip(shares.iter_mut()) {
+            if left > 0 && weight(*i) > 0 {
+                *share += 1;
+                left -= 1;
+            }
+        }
+        // Items out of their bounds are frozen at the bound, and the rest shared again
+        let mut clamped = false;
+        for (i, share) in free.iter().zip(&shares) {
+            let it = &items[*i];
+            let sz = (*share)
+                .min(it.max.unwrap_or(i32::MAX))
+                .max(it.min.unwrap_or(0));
+            if sz != *share {
+                sizes[*i] = Some(sz);
+                clamped = true;
+            }
+        }
+        if !clamped {
+            for (i, share) in free.into_iter().zip(shares) {
+                sizes[i] = Some(share);
+            }
+        }
+    }
+    sizes.into_iter().map(|sz| sz.unwrap_or(0)).collect()
+}
+
+/// Lays out the children of the flex again, after Fl_Flex's own layout in `resize`.
+fn layout_flex(f: &mut group::Flex, layout: &FlexLayout) {
+    let row = f.get_type::<group::FlexType>() == group::FlexType::Row;
+    let (ml, mt, mr, mb) = f.margins();
+    let frame = f.frame();
+    let x = f.x() + frame.dx() + ml;
+    let y = f.y() + frame.dy() + mt;
+    let w = f.w() - frame.dw() - ml - mr;
+    let h = f.h() - frame.dh() - mt - mb;
+    let mut children = vec![];
+    let mut items = vec![];
+    for i in 0..f.children() {
+        if let Some(c) = f.child(i) {
+            if c.visible() {
+                children.push(c);
+                items.push(layout.items.get(i as usize).cloned().unwrap_or_default());
             }
         }
-        if oldg.children.len() > newg.children.len() {
-            for i in newg.children.len()..oldg.children.len() {
-                $crate::utils::subtree::remove_subtree(&mut oldg.children[i], $dom);
+    }
+    let pad = f.pad();
+    let gaps = (children.len() as i32 - 1).max(0) * pad;
+    let (main, cross) = if row { (w, h) } else { (h, w) };
+    let sizes = flex_sizes(main - gaps, &items);
+    let mut pos = if row { x } else { y };
+    for ((mut c, it), sz) in children.into_iter().zip(items).zip(sizes) {
+        let own = if row { it.h } else { it.w };
+        let (offset, len) = match (it.align.unwrap_or(layout.align), own) {
+            (FlexAlign::Stretch, _) | (_, None) => (0, cross),
+            (align, Some(len)) => {
+                let len = len.min(cross);
+                let offset = match align {
+                    FlexAlign::Start => 0,
+                    FlexAlign::Center => (cross - len) / 2,
+                    _ => cross - len,
+                };
+                (offset, len)
             }
+        };
+        if row {
+            c.resize(pos, y + offset, sz, len);
+        } else {
+            c.resize(x + offset, pos, len, sz);
         }
+        pos += sz + pad;
+    }
+}
+
+/// Resizes the flex in place, which lays it out and clears its pending layout.
+fn relayout_flex<Message>(dom: &VirtualDom<Message>, id: usize) {
+    let f = match dom.widget_map.borrow().get(&id) {
+        Some(WidgetUnion::Column(f)) | Some(WidgetUnion::Row(f)) => Some(f.clone()),
+        _ => None,
     };
+    if let Some(mut f) = f {
+        f.resize(f.x(), f.y(), f.w(), f.h());
+    }
 }
 
 #[derive(Clone)]
@@ -50,19 +217,21 @@ pub struct Column<Message> {
     gprops: GroupProps<Message>,
     margins: (i32, i32, i32, i32),
     padding: i32,
+    layout: Rc<RefCell<FlexLayout>>,
 }
 
-impl<Message> Column<Message> {
+impl<Message: 'static> Column<Message> {
     pub fn new(children: &[View<Message>]) -> Self {
+        let mut children = children.to_vec();
+        let layout = FlexLayout::new(&mut children);
         Self {
             node_id: 0,
             typ: VNodeType::Column,
             wprops: WidgetProps::default(),
-            gprops: GroupProps {
-                children: children.to_vec(),
-            },
+            gprops: GroupProps { children },
             margins: (0, 0, 0, 0),
             padding: 0,
+            layout: Rc::new(RefCell::new(layout)),
         }
     }
     pub fn margins(mut self, l: i32, t: i32, r: i32, b: i32) -> Self {
@@ -73,6 +242,11 @@ impl<Message> Column<Message> {
         self.padding = p;
         self
     }
+    /// Sets the cross-axis alignment of the children, which stretch by default.
+    pub fn align_items(self, align: FlexAlign) -> Self {
+        self.layout.borrow_mut().align = align;
+        self
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Column<Message> {
@@ -86,17 +260,33 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Column<Message>
             let (l, t, r, bot) = self.margins;
             col.set_margins(l, t, r, bot);
             col.set_pad(self.padding);
+            let layout = self.layout.clone();
+            col.resize_callback(move |f, _, _, _, _| layout_flex(f, &layout.borrow()));
             col.begin();
             for child in &self.gprops.children {
                 child.mount(dom);
             }
             col.end();
+            col.resize(col.x(), col.y(), col.w(), col.h());
         });
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
         let b;
-        default_patch!(b, self, old, dom, Column);
+        default_patch!(b, self, old, dom, Column, {
+            let old: &Column<Message> = old.as_any().downcast_ref().unwrap();
+            if self.margins != old.margins {
+                let (l, t, r, bot) = self.margins;
+                b.set_margins(l, t, r, bot);
+            }
+            if self.padding != old.padding {
+                b.set_pad(self.padding);
+            }
+            // The mounted flex reads the layout of the old node
+            *old.layout.borrow_mut() = self.layout.borrow().clone();
+            self.layout = old.layout.clone();
+        });
         update_group_children!(old, self, dom, Column);
+        relayout_flex(dom, self.node_id);
     }
 }
 
@@ -108,19 +298,21 @@ pub struct Row<Message> {
     gprops: GroupProps<Message>,
     margins: (i32, i32, i32, i32),
     padding: i32,
+    layout: Rc<RefCell<FlexLayout>>,
 }
 
-impl<Message> Row<Message> {
+impl<Message: 'static> Row<Message> {
     pub fn new(children: &[View<Message>]) -> Self {
+        let mut children = children.to_vec();
+        let layout = FlexLayout::new(&mut children);
         Self {
             node_id: 0,
             typ: VNodeType::Row,
             wprops: WidgetProps::default(),
-            gprops: GroupProps {
-                children: children.to_vec(),
-            },
+            gprops: GroupProps { children },
             margins: (0, 0, 0, 0),
             padding: 0,
+            layout: Rc::new(RefCell::new(layout)),
         }
     }
     pub fn margins(mut self, l: i32, t: i32, r: i32, b: i32) -> Self {
@@ -131,6 +323,11 @@ impl<Message> Row<Message> {
         self.padding = p;
         self
     }
+    /// Sets the cross-axis alignment of the children, which stretch by default.
+    pub fn align_items(self, align: FlexAlign) -> Self {
+        self.layout.borrow_mut().align = align;
+        self
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Row<Message> {
@@ -144,11 +341,14 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Row<Message> {
             let (l, t, r, bot) = self.margins;
             row.set_margins(l, t, r, bot);
             row.set_pad(self.padding);
+            let layout = self.layout.clone();
+            row.resize_callback(move |f, _, _, _, _| layout_flex(f, &layout.borrow()));
             row.begin();
             for child in &self.gprops.children {
                 child.mount(dom);
             }
             row.end();
+            row.resize(row.x(), row.y(), row.w(), row.h());
         });
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
@@ -162,8 +362,12 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Row<Message> {
             if self.padding != old.padding {
                 b.set_pad(self.padding);
             }
+            // The mounted flex reads the layout of the old node
+            *old.layout.borrow_mut() = self.layout.borrow().clone();
+            self.layout = old.layout.clone();
         });
         update_group_children!(old, self, dom, Row);
+        relayout_flex(dom, self.node_id);
     }
 }
 
@@ -343,10 +547,165 @@ macro_rules! define_group {
 }
 
 define_group!(Group);
-define_group!(Scroll);
 define_group!(Pack);
 define_group!(Wizard);
 
+#[derive(Clone)]
+pub struct Scroll<Message> {
+    node_id: usize,
+    typ: VNodeType,
+    wprops: WidgetProps,
+    gprops: GroupProps<Message>,
+    position: Option<(i32, i32)>,
+    scrollbars: Option<group::ScrollType>,
+    scrollbar_size: Option<i32>,
+    #[allow(clippy::type_complexity)]
+    on_scroll: Option<Rc<Box<dyn Fn(i32, i32) -> Message>>>,
+}
+
+impl<Message> Scroll<Message> {
+    pub fn new(children: &[View<Message>]) -> Self {
+        Self {
+            node_id: 0,
+            typ: VNodeType::Scroll,
+            wprops: WidgetProps::default(),
+            gprops: GroupProps {
+                children: children.to_vec(),
+            },
+            position: None,
+            scrollbars: None,
+            scrollbar_size: None,
+            on_scroll: None,
+        }
+    }
+    /// Scrolls to the position when mounted and whenever the position changes. Scrolling
+    /// by the user is kept until then, `on_scroll` reporting it.
+    pub fn scroll_to(mut self, x: i32, y: i32) -> Self {
+        self.position = Some((x, y));
+        self
+    }
+    /// Which scrollbars are shown, both when needed by default.
+    pub fn scrollbars(mut self, typ: group::ScrollType) -> Self {
+        self.scrollbars = Some(typ);
+        self
+    }
+    pub fn scrollbar_size(mut self, sz: i32) -> Self {
+        self.scrollbar_size = Some(sz);
+        self
+    }
+    /// Called with the new position whenever the contents are scrolled.
+    pub fn on_scroll<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_scroll = Some(Rc::new(Box::new(f)));
+        self
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> Scroll<Message> {
+    // Fl_Scroll has no callback of its own, the position is checked when it is redrawn
+    fn set_callback(&self, b: &mut group::Scroll, dom: &VirtualDom<Message>) {
+        let on_scroll = self.on_scroll.clone();
+        let sender = dom.emitter();
+        let mut last = (b.xposition(), b.yposition());
+        b.draw(move |b| {
+            let pos = (b.xposition(), b.yposition());
+            if pos != last {
+                last = pos;
+                if let Some(on_scroll) = &on_scroll {
+                    sender.emit(on_scroll(pos.0, pos.1));
+                }
+            }
+        });
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Scroll<Message> {
+    default_impl!();
+    fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
+        Some(&mut self.gprops)
+    }
+    fn mount(&self, dom: &VirtualDom<Message>) {
+        let mut g = group::Scroll::default();
+        default_mount!(g, self, dom, Scroll, {
+            if let Some(typ) = self.scrollbars {
+                g.set_type(typ);
+            }
+            if let Some(sz) = self.scrollbar_size {
+                g.set_scrollbar_size(sz);
+            }
+            g.begin();
+            for child in &self.gprops.children {
+                child.mount(dom);
+            }
+            g.end();
+            g.fix_layout();
+            if let Some((x, y)) = self.position {
+                g.scroll_to(x, y);
+            }
+            self.set_callback(&mut g, dom);
+        });
+    }
+    fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
+        let b;
+        let mut moved = false;
+        default_patch!(b, self, old, dom, Scroll, {
+            let old: &Scroll<Message> = old.as_any().downcast_ref().unwrap();
+            moved = self.position != old.position;
+            if self.scrollbars != old.scrollbars {
+                b.set_type(self.scrollbars.unwrap_or(group::ScrollType::Both));
+            }
+            if self.scrollbar_size != old.scrollbar_size {
+                // 0 is the global scrollbar size
+                b.set_scrollbar_size(self.scrollbar_size.unwrap_or(0));
+            }
+            self.set_callback(b, dom);
+        });
+        update_group_children!(old, self, dom, Scroll);
+        // Scrolled once the children are in place
+        if let Some((x, y)) = self.position.filter(|_| moved) {
+            let b = match dom.widget_map.borrow().get(&self.node_id) {
+                Some(WidgetUnion::Scroll(b)) => Some(b.clone()),
+                _ => None,
+            };
+            if let Some(mut b) = b {
+                b.scroll_to(x, y);
+            }
+        }
+    }
+}
+
+/// Scrolls `s` so that its descendant `w` is fully visible.
+pub(crate) fn scroll_into_view(s: &mut group::Scroll, w: &widget::Widget) {
+    let frame = s.frame();
+    let mut vw = s.w() - frame.dw();
+    let mut vh = s.h() - frame.dh();
+    let vbar = s.scrollbar();
+    if vbar.visible() {
+        vw -= vbar.w();
+    }
+    let hbar = s.hscrollbar();
+    if hbar.visible() {
+        vh -= hbar.h();
+    }
+    let (x0, y0) = (s.xposition(), s.yposition());
+    // The widget's position in the scrolled contents
+    let cx = w.x() - s.x() - frame.dx() + x0;
+    let cy = w.y() - s.y() - frame.dy() + y0;
+    let into_view = |pos: i32, start: i32, len: i32, visible: i32| {
+        if start < pos {
+            start
+        } else if start + len > pos + visible {
+            (start + len - visible).min(start)
+        } else {
+            pos
+        }
+    };
+    let x = into_view(x0, cx, w.w(), vw);
+    let y = into_view(y0, cy, w.h(), vh);
+    if (x, y) != (x0, y0) {
+        s.scroll_to(x, y);
+    }
+}
+
 #[derive(Clone)]
 pub struct Tabs<Message> {
     node_id: usize,
@@ -426,10 +785,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Tabs<Message> {
             }
 
             if let Some(cb) = self.on_change.clone() {
+                let sender = dom.emitter();
                 g.set_callback(move |t| {
                     if let Some(val) = t.value() {
                         let lbl = val.label();
-                        app::Sender::<Message>::get().send(cb(lbl));
+                        sender.emit(cb(lbl));
                     }
                 });
             }
@@ -467,11 +827,12 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Tabs<Message> {
             }
             if self.on_change.is_some() != old.on_change.is_some() {
                 let cb = self.on_change.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |t| {
                     if let Some(cb) = &cb {
                         if let Some(val) = t.value() {
                             let lbl = val.label();
-                            app::Sender::<Message>::get().send(cb(lbl));
+                            sender.emit(cb(lbl));
                         }
                     }
                 });
@@ -492,6 +853,10 @@ pub struct Grid<Message> {
     cols: i32,
     gap_x: i32,
     gap_y: i32,
+    row_weights: Vec<i32>,
+    col_weights: Vec<i32>,
+    row_heights: Vec<i32>,
+    col_widths: Vec<i32>,
 }
 
 impl<Message> Grid<Message> {
@@ -507,6 +872,10 @@ impl<Message> Grid<Message> {
             cols: 0,
             gap_x: 0,
             gap_y: 0,
+            row_weights: vec![],
+            col_weights: vec![],
+            row_heights: vec![],
+            col_widths: vec![],
         }
     }
     pub fn layout(mut self, rows: i32, cols: i32) -> Self {
@@ -519,6 +888,50 @@ impl<Message> Grid<Message> {
         self.gap_y = y;
         self
     }
+    /// How the free height is shared between the rows, 50 each by default.
+    pub fn row_weights(mut self, weights: &[i32]) -> Self {
+        self.row_weights = weights.to_vec();
+        self
+    }
+    /// How the free width is shared between the columns, 50 each by default.
+    pub fn col_weights(mut self, weights: &[i32]) -> Self {
+        self.col_weights = weights.to_vec();
+        self
+    }
+    /// The minimum height of each row.
+    pub fn row_heights(mut self, heights: &[i32]) -> Self {
+        self.row_heights = heights.to_vec();
+        self
+    }
+    /// The minimum width of each column.
+    pub fn col_widths(mut self, widths: &[i32]) -> Self {
+        self.col_widths = widths.to_vec();
+        self
+    }
+    fn set_tracks(&self, g: &mut group::Grid) {
+        for (i, v) in self.row_weights.iter().enumerate() {
+            g.set_row_weight(i as i32, *v);
+        }
+        for (i, v) in self.col_weights.iter().enumerate() {
+            g.set_col_weight(i as i32, *v);
+        }
+        for (i, v) in self.row_heights.iter().enumerate() {
+            g.set_row_height(i as i32, *v);
+        }
+        for (i, v) in self.col_widths.iter().enumerate() {
+            g.set_col_width(i as i32, *v);
+        }
+    }
+}
+
+/// Sets the row or column values which changed, resetting the dropped ones to `default`.
+fn update_tracks(old: &[i32], new: &[i32], default: i32, mut set: impl FnMut(i32, i32)) {
+    for i in 0..old.len().max(new.len()) {
+        let v = new.get(i).copied().unwrap_or(default);
+        if old.get(i).copied().unwrap_or(default) != v {
+            set(i as i32, v);
+        }
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Grid<Message> {
@@ -533,6 +946,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Grid<Message> {
                 g.set_layout(self.rows, self.cols);
             }
             g.set_gap(self.gap_x, self.gap_y);
+            self.set_tracks(&mut g);
             g.begin();
             for child in &self.gprops.children {
                 child.mount(dom);
@@ -545,10 +959,27 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Grid<Message> {
         let b;
         default_patch!(b, self, old, dom, Grid, {
             let old: &Grid<Message> = old.as_any().downcast_ref().unwrap();
-            if (self.rows != old.rows || self.cols != old.cols) && (self.rows > 0 || self.cols > 0)
-            {
+            let relayout = (self.rows != old.rows || self.cols != old.cols)
+                && (self.rows > 0 || self.cols > 0);
+            if relayout {
                 b.set_layout(self.rows, self.cols);
             }
+            update_tracks(&old.row_weights, &self.row_weights, 50, |i, v| {
+                b.set_row_weight(i, v)
+            });
+            update_tracks(&old.col_weights, &self.col_weights, 50, |i, v| {
+                b.set_col_weight(i, v)
+            });
+            update_tracks(&old.row_heights, &self.row_heights, 0, |i, v| {
+                b.set_row_height(i, v)
+            });
+            update_tracks(&old.col_widths, &self.col_widths, 0, |i, v| {
+                b.set_col_width(i, v)
+            });
+            if relayout {
+                // Added rows and columns start with the default values
+                self.set_tracks(b);
+            }
             if self.gap_x != old.gap_x || self.gap_y != old.gap_y {
                 b.set_gap(self.gap_x, self.gap_y);
             }
@@ -55,6 +55,9 @@ macro_rules! define_input {
 
         impl<Message: Clone + 'static + Send + Sync> VNode<Message> for $name<Message> {
             default_impl!();
+            fn tprops(&mut self) -> Option<&mut TextProps> {
+                Some(&mut self.tprops)
+            }
             fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
                 None
             }
@@ -65,16 +68,17 @@ macro_rules! define_input {
                     b.set_value(&self.iprops.value);
                     let change_cb = self.change_cb.clone();
                     let enter_cb = self.enter_cb.clone();
+                    let sender = dom.emitter();
                     b.set_callback(move |b| {
                         let v = b.value();
                         if let Some(change_cb) = &change_cb {
-                            app::Sender::<Message>::get().send(change_cb(v.clone()));
+                            sender.emit(change_cb(v.clone()));
                         }
                         if let Some(enter_cb) = &enter_cb {
                             if app::event() == enums::Event::KeyDown
                                 && app::event_key() == enums::Key::Enter
                             {
-                                app::Sender::<Message>::get().send(enter_cb(v));
+                                sender.emit(enter_cb(v));
                             }
                         }
                     });
@@ -91,16 +95,17 @@ macro_rules! define_input {
                         b.set_value(&newi.value);
                         let change_cb = self.change_cb.clone();
                         let enter_cb = self.enter_cb.clone();
+                        let sender = dom.emitter();
                         b.set_callback(move |b| {
                             let v = b.value();
                             if let Some(change_cb) = &change_cb {
-                                app::Sender::<Message>::get().send(change_cb(v.clone()));
+                                sender.emit(change_cb(v.clone()));
                             }
                             if let Some(enter_cb) = &enter_cb {
                                 if app::event() == enums::Event::KeyDown
                                     && app::event_key() == enums::Key::Enter
                                 {
-                                    app::Sender::<Message>::get().send(enter_cb(v));
+                                    sender.emit(enter_cb(v));
                                 }
                             }
                         });
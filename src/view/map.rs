@@ -0,0 +1,98 @@
+use crate::props::*;
+use crate::vdom::VirtualDom;
+use crate::vnode::{AnyNode, VNode, VNodeType, View};
+use std::rc::Rc;
+
+/// Wraps a subtree emitting `M` so that it can be embedded in a view emitting `N`.
+#[derive(Clone)]
+pub struct Map<M, N> {
+    typ: VNodeType,
+    inner: View<M>,
+    mapper: Rc<dyn Fn(M) -> N>,
+}
+
+impl<M: Clone + 'static + Send + Sync, N: Clone + 'static + Send + Sync> Map<M, N> {
+    pub fn new<F: 'static + Fn(M) -> N>(inner: View<M>, f: F) -> Self {
+        Self {
+            typ: VNodeType::Other(std::any::TypeId::of::<Self>()),
+            inner,
+            mapper: Rc::new(f),
+        }
+    }
+}
+
+impl<M: Clone + 'static + Send + Sync, N: Clone + 'static + Send + Sync> VNode<N> for Map<M, N> {
+    // The wrapper owns no widget, it is identified by the root of its subtree
+    fn node_id(&self) -> usize {
+        self.inner.node_id()
+    }
+    fn set_node_id(&mut self, id: usize) {
+        self.inner.set_node_id(id);
+    }
+    fn typ(&self) -> &VNodeType {
+        &self.typ
+    }
+    fn wprops(&mut self) -> &mut WidgetProps {
+        self.inner.wprops()
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+    fn gprops(&mut self) -> Option<&mut GroupProps<N>> {
+        None
+    }
+    fn mount(&self, dom: &VirtualDom<N>) {
+        self.inner.mount(&dom.map(self.mapper.clone()));
+    }
+    fn patch(&mut self, old: &mut View<N>, dom: &VirtualDom<N>) {
+        if self.typ != *old.typ() {
+            crate::utils::subtree::replace_subtree(old, self, dom);
+            return;
+        }
+        let old: &mut Map<M, N> = old.as_any_mut().downcast_mut().unwrap();
+        self.inner
+            .patch(&mut old.inner, &dom.map(self.mapper.clone()));
+    }
+    fn unmount(&mut self, dom: &VirtualDom<N>) {
+        self.inner.unmount(&dom.map(self.mapper.clone()));
+    }
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        self.inner.tprops()
+    }
+    fn apply_styles(&mut self, sheet: &crate::style::StyleSheet, inherited: &crate::style::Style) {
+        self.inner.apply_styles(sheet, inherited);
+    }
+    fn mapped(&mut self) -> Option<&mut dyn AnyNode> {
+        Some(&mut self.inner)
+    }
+    fn assign_ids_topdown(&mut self) {
+        self.inner.assign_ids_topdown();
+    }
+}
+
+pub trait ViewExt<M> {
+    /// Maps the messages emitted by this view, so that a component with its own message type
+    /// can be embedded in a parent view.
+    ///
+    /// Messages can't be mapped back, so the `on_command` subscribers inside this view are
+    /// only given the messages emitted within it. The messages handled by the application,
+    /// like those of tasks, don't reach them. Widgets driven by commands from elsewhere
+    /// have to use the application's message type.
+    fn map<N, F>(self, f: F) -> View<N>
+    where
+        N: Clone + 'static + Send + Sync,
+        F: 'static + Fn(M) -> N;
+}
+
+impl<M: Clone + 'static + Send + Sync> ViewExt<M> for View<M> {
+    fn map<N, F>(self, f: F) -> View<N>
+    where
+        N: Clone + 'static + Send + Sync,
+        F: 'static + Fn(M) -> N,
+    {
+        Box::new(Map::new(self, f))
+    }
+}
@@ -1,23 +1,22 @@
 use crate::props::*;
 use crate::utils::macros::*;
-use crate::vdom::VirtualDom;
+use crate::vdom::{Emitter, VirtualDom};
 use crate::vnode::{VNode, VNodeType, View};
-use crate::widgets::WidgetUnion;
+use crate::widgets::{IsWidget, WidgetUnion};
 pub use fltk::menu::MenuFlag;
 use fltk::{prelude::*, *};
+use std::cell::RefCell;
+use std::rc::Rc;
 
+/// An entry of a menu. Items with children are submenus, whose children's paths
+/// are prefixed with the submenu's label.
 #[derive(Debug, Clone)]
 pub struct MenuItem<Message> {
     label: String,
     shortcut: enums::Shortcut,
     flags: menu::MenuFlag,
-    callback: Message,
-}
-
-impl<Message> PartialEq for MenuItem<Message> {
-    fn eq(&self, other: &Self) -> bool {
-        self.label == other.label && self.shortcut == other.shortcut && self.flags == other.flags
-    }
+    message: Option<Message>,
+    children: Vec<MenuItem<Message>>,
 }
 
 impl<Message> MenuItem<Message> {
@@ -31,8 +30,245 @@ impl<Message> MenuItem<Message> {
             label: label.to_string(),
             shortcut,
             flags,
-            callback,
+            message: Some(callback),
+            children: vec![],
+        }
+    }
+    pub fn action(label: &str, message: Message) -> Self {
+        Self::new(label, enums::Shortcut::None, MenuFlag::Normal, message)
+    }
+    /// A toggle item, whose check mark follows `checked`.
+    pub fn check(label: &str, checked: bool, message: Message) -> Self {
+        let value = if checked {
+            MenuFlag::Value
+        } else {
+            MenuFlag::Normal
+        };
+        Self::new(
+            label,
+            enums::Shortcut::None,
+            MenuFlag::Toggle | value,
+            message,
+        )
+    }
+    /// An item of a radio group, which spans the adjacent radio items up to a divider.
+    pub fn radio(label: &str, selected: bool, message: Message) -> Self {
+        let value = if selected {
+            MenuFlag::Value
+        } else {
+            MenuFlag::Normal
+        };
+        Self::new(
+            label,
+            enums::Shortcut::None,
+            MenuFlag::Radio | value,
+            message,
+        )
+    }
+    pub fn submenu(label: &str, children: &[MenuItem<Message>]) -> Self
+    where
+        Message: Clone,
+    {
+        Self {
+            label: label.to_string(),
+            shortcut: enums::Shortcut::None,
+            flags: MenuFlag::Submenu,
+            message: None,
+            children: children.to_vec(),
+        }
+    }
+    pub fn shortcut(mut self, shortcut: enums::Shortcut) -> Self {
+        self.shortcut = shortcut;
+        self
+    }
+    pub fn enabled(mut self, flag: bool) -> Self {
+        if flag {
+            self.flags &= !MenuFlag::Inactive;
+        } else {
+            self.flags |= MenuFlag::Inactive;
+        }
+        self
+    }
+    /// Draws a divider line after the item.
+    pub fn divider(mut self) -> Self {
+        self.flags |= MenuFlag::MenuDivider;
+        self
+    }
+}
+
+/// Builds a tree of menu items.
+#[derive(Debug, Clone)]
+pub struct Menu<Message> {
+    items: Vec<MenuItem<Message>>,
+}
+
+impl<Message> Default for Menu<Message> {
+    fn default() -> Self {
+        Self { items: vec![] }
+    }
+}
+
+impl<Message: Clone> Menu<Message> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn item(mut self, item: MenuItem<Message>) -> Self {
+        self.items.push(item);
+        self
+    }
+    pub fn action(self, label: &str, message: Message) -> Self {
+        self.item(MenuItem::action(label, message))
+    }
+    pub fn check(self, label: &str, checked: bool, message: Message) -> Self {
+        self.item(MenuItem::check(label, checked, message))
+    }
+    pub fn radio(self, label: &str, selected: bool, message: Message) -> Self {
+        self.item(MenuItem::radio(label, selected, message))
+    }
+    pub fn submenu(self, label: &str, menu: Menu<Message>) -> Self {
+        self.item(MenuItem::submenu(label, &menu.items))
+    }
+    /// Draws a divider line after the last item.
+    pub fn divider(mut self) -> Self {
+        if let Some(last) = self.items.pop() {
+            self.items.push(last.divider());
+        }
+        self
+    }
+    pub fn items(&self) -> &[MenuItem<Message>] {
+        &self.items
+    }
+}
+
+// Flags which can be updated in place, without rebuilding the menu
+fn dynamic_flags() -> MenuFlag {
+    MenuFlag::Value | MenuFlag::Inactive
+}
+
+/// Whether the menus only differ in their messages and dynamic flags.
+fn same_layout<Message>(a: &[MenuItem<Message>], b: &[MenuItem<Message>]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.label == b.label
+                && a.shortcut == b.shortcut
+                && a.flags & !dynamic_flags() == b.flags & !dynamic_flags()
+                && a.message.is_some() == b.message.is_some()
+                && same_layout(&a.children, &b.children)
+        })
+}
+
+fn item_path(prefix: &str, label: &str) -> String {
+    if prefix.is_empty() {
+        label.to_string()
+    } else {
+        format!("{}/{}", prefix, label)
+    }
+}
+
+fn collect_messages<Message: Clone>(items: &[MenuItem<Message>], out: &mut Vec<Message>) {
+    for item in items {
+        out.extend(item.message.clone());
+        collect_messages(&item.children, out);
+    }
+}
+
+/// The messages of a mounted menu, in item order, read when an item is picked
+/// so that they are always those of the latest view.
+type MenuMessages<Message> = Rc<RefCell<Vec<Message>>>;
+
+fn add_items<M, Message>(
+    b: &mut M,
+    items: &[MenuItem<Message>],
+    prefix: &str,
+    messages: &MenuMessages<Message>,
+    next: &mut usize,
+    sender: &Emitter<Message>,
+) where
+    M: MenuExt,
+    Message: Clone + 'static,
+{
+    for item in items {
+        let path = item_path(prefix, &item.label);
+        if item.message.is_some() {
+            let idx = *next;
+            *next += 1;
+            let messages = messages.clone();
+            let sender = sender.clone();
+            b.add(&path, item.shortcut, item.flags, move |_| {
+                let msg = messages.borrow().get(idx).cloned();
+                if let Some(msg) = msg {
+                    sender.emit(msg);
+                }
+            });
+        } else {
+            b.add(&path, item.shortcut, item.flags, |_| ());
+        }
+        add_items(b, &item.children, &path, messages, next, sender);
+    }
+}
+
+/// Applies the check marks and enabled states of `items` to the mounted menu,
+/// also undoing toggles the model did not follow.
+fn sync_flags<M: MenuExt, Message>(b: &M, items: &[MenuItem<Message>], prefix: &str) {
+    for item in items {
+        let path = item_path(prefix, &item.label);
+        if let Some(mut it) = b.find_item(&path) {
+            if item.flags.intersects(MenuFlag::Toggle | MenuFlag::Radio) {
+                let checked = item.flags.contains(MenuFlag::Value);
+                if it.value() != checked {
+                    if checked {
+                        it.set();
+                    } else {
+                        it.clear();
+                    }
+                }
+            }
+            let active = !item.flags.contains(MenuFlag::Inactive);
+            if it.active() != active {
+                if active {
+                    it.activate();
+                } else {
+                    it.deactivate();
+                }
+            }
         }
+        sync_flags(b, &item.children, &path);
+    }
+}
+
+fn build_menu<M, Message>(
+    b: &mut M,
+    items: &[MenuItem<Message>],
+    messages: &MenuMessages<Message>,
+    sender: &Emitter<Message>,
+) where
+    M: MenuExt,
+    Message: Clone + 'static,
+{
+    let mut msgs = vec![];
+    collect_messages(items, &mut msgs);
+    *messages.borrow_mut() = msgs;
+    add_items(b, items, "", messages, &mut 0, sender);
+}
+
+fn patch_menu<M, Message>(
+    b: &mut M,
+    new: &[MenuItem<Message>],
+    old: &[MenuItem<Message>],
+    messages: &MenuMessages<Message>,
+    sender: &Emitter<Message>,
+) where
+    M: MenuExt,
+    Message: Clone + 'static,
+{
+    if same_layout(new, old) {
+        let mut msgs = vec![];
+        collect_messages(new, &mut msgs);
+        *messages.borrow_mut() = msgs;
+        sync_flags(b, new, "");
+    } else {
+        b.clear();
+        build_menu(b, new, messages, sender);
     }
 }
 
@@ -45,6 +281,7 @@ macro_rules! define_menu {
             wprops: WidgetProps,
             tprops: TextProps,
             items: Vec<MenuItem<Message>>,
+            messages: MenuMessages<Message>,
         }
 
         impl<Message: Clone> $name<Message> {
@@ -55,12 +292,19 @@ macro_rules! define_menu {
                     wprops: WidgetProps::default(),
                     tprops: TextProps::default(),
                     items: items.to_vec(),
+                    messages: Rc::new(RefCell::new(vec![])),
                 }
             }
+            pub fn from_menu(menu: Menu<Message>) -> Self {
+                Self::new(&menu.items)
+            }
         }
 
         impl<Message: Clone + 'static + Send + Sync> VNode<Message> for $name<Message> {
             default_impl!();
+            fn tprops(&mut self) -> Option<&mut TextProps> {
+                Some(&mut self.tprops)
+            }
             fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
                 None
             }
@@ -68,16 +312,7 @@ macro_rules! define_menu {
                 let mut b = menu::$name::default();
                 default_mount!(b, self, dom, $name, {
                     set_tprops!(b, self.tprops);
-                    for item in &self.items {
-                        let sender: app::Sender<Message> = app::Sender::get();
-                        b.add_emit(
-                            &item.label,
-                            item.shortcut,
-                            item.flags,
-                            sender,
-                            item.callback.clone(),
-                        );
-                    }
+                    build_menu(&mut b, &self.items, &self.messages, &dom.emitter());
                 });
             }
             fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
@@ -85,63 +320,8 @@ macro_rules! define_menu {
                 default_patch!(b, self, old, dom, $name, {
                     let old: &$name<Message> = old.as_any().downcast_ref().unwrap();
                     update_tprops!(b, self.tprops, old.tprops);
-                    // Diff menu items to avoid full rebuild when possible
-                    if self.items != old.items {
-                        // Compute common prefix length
-                        let mut prefix = 0usize;
-                        let min_len = self.items.len().min(old.items.len());
-                        while prefix < min_len && self.items[prefix] == old.items[prefix] {
-                            prefix += 1;
-                        }
-
-                        // Compute common suffix length, not crossing the prefix
-                        let mut suffix = 0usize;
-                        let old_len = old.items.len();
-                        let new_len = self.items.len();
-                        while suffix < (old_len - prefix).min(new_len - prefix)
-                            && self.items[new_len - 1 - suffix] == old.items[old_len - 1 - suffix]
-                        {
-                            suffix += 1;
-                        }
-
-                        if prefix == old.items.len() && new_len > old_len {
-                            // Pure append
-                            for item in &self.items[prefix..] {
-                                let sender: app::Sender<Message> = app::Sender::get();
-                                b.add_emit(
-                                    &item.label,
-                                    item.shortcut,
-                                    item.flags,
-                                    sender,
-                                    item.callback.clone(),
-                                );
-                            }
-                        } else if prefix == new_len && old_len > new_len {
-                            // Pure truncate
-                            for idx in (new_len as i32..old_len as i32).rev() {
-                                b.remove(idx);
-                            }
-                        } else {
-                            // Middle-diff: reuse prefix and suffix; rebuild only the middle
-                            let old_mid_end = old_len - suffix;
-                            let new_mid_end = new_len - suffix;
-                            // Remove the old middle segment from end to start so indices stay valid
-                            for idx in ((prefix as i32)..(old_mid_end as i32)).rev() {
-                                b.remove(idx);
-                            }
-                            // Append the new middle segment
-                            for item in &self.items[prefix..new_mid_end] {
-                                let sender: app::Sender<Message> = app::Sender::get();
-                                b.add_emit(
-                                    &item.label,
-                                    item.shortcut,
-                                    item.flags,
-                                    sender,
-                                    item.callback.clone(),
-                                );
-                            }
-                        }
-                    }
+                    self.messages = old.messages.clone();
+                    patch_menu(b, &self.items, &old.items, &self.messages, &dom.emitter());
                 });
             }
         }
@@ -152,6 +332,121 @@ define_menu!(MenuBar);
 define_menu!(SysMenuBar);
 define_menu!(MenuButton);
 
+#[derive(Clone)]
+struct ContextMenuWidget<Message> {
+    g: group::Group,
+    popup: menu::MenuButton,
+    messages: MenuMessages<Message>,
+}
+
+impl<Message: 'static> IsWidget for ContextMenuWidget<Message> {
+    fn as_widget(&self) -> fltk::widget::Widget {
+        unsafe { self.g.into_widget() }
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Wraps a view, popping up a menu when it is right-clicked.
+#[derive(Clone)]
+pub struct ContextMenu<Message> {
+    node_id: usize,
+    typ: VNodeType,
+    wprops: WidgetProps,
+    gprops: GroupProps<Message>,
+    items: Vec<MenuItem<Message>>,
+}
+
+impl<Message: Clone + 'static> ContextMenu<Message> {
+    pub fn new(child: View<Message>, menu: Menu<Message>) -> Self {
+        Self {
+            node_id: 0,
+            typ: VNodeType::Other(std::any::TypeId::of::<ContextMenuWidget<Message>>()),
+            wprops: WidgetProps::default(),
+            gprops: GroupProps {
+                children: vec![child],
+            },
+            items: menu.items,
+        }
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> VNode<Message> for ContextMenu<Message> {
+    default_impl!();
+    fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
+        Some(&mut self.gprops)
+    }
+    fn mount(&self, dom: &VirtualDom<Message>) {
+        let mut g = group::Group::default();
+        set_wprops(&mut g, &self.wprops);
+        g.begin();
+        for child in &self.gprops.children {
+            child.mount(dom);
+        }
+        // A popup menu button only claims right-clicks, letting other events reach the child
+        let mut popup = menu::MenuButton::default();
+        popup.set_type(menu::MenuButtonType::Popup3);
+        g.end();
+        g.resize_callback(|g, x, y, w, h| {
+            for mut ch in g.clone().into_iter() {
+                ch.resize(x, y, w, h);
+            }
+        });
+        let messages = Rc::new(RefCell::new(vec![]));
+        build_menu(&mut popup, &self.items, &messages, &dom.emitter());
+        dom.widget_map.borrow_mut().insert(
+            self.node_id,
+            WidgetUnion::Other(Rc::new(ContextMenuWidget { g, popup, messages })),
+        );
+    }
+    fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
+        if self.typ != *old.typ() {
+            crate::utils::subtree::replace_subtree(old, self, dom);
+            return;
+        }
+        self.set_node_id(old.node_id());
+        let w = {
+            let mut map = dom.widget_map.borrow_mut();
+            let Some(WidgetUnion::Other(w)) = map.get_mut(&old.node_id()) else {
+                return;
+            };
+            update_wprops(&mut w.as_widget(), old.wprops(), &self.wprops);
+            w.as_any()
+                .downcast_ref::<ContextMenuWidget<Message>>()
+                .unwrap()
+                .clone()
+        };
+        let ContextMenuWidget {
+            mut g,
+            mut popup,
+            messages,
+        } = w;
+        {
+            let old: &ContextMenu<Message> = old.as_any().downcast_ref().unwrap();
+            patch_menu(
+                &mut popup,
+                &self.items,
+                &old.items,
+                &messages,
+                &dom.emitter(),
+            );
+        }
+        let old = old.gprops().unwrap();
+        // A replaced child is mounted into the group, and the popup has to stay on top of it
+        g.begin();
+        self.gprops.children[0].patch(&mut old.children[0], dom);
+        g.end();
+        g.add(&popup);
+        for mut ch in g.clone().into_iter() {
+            ch.resize(g.x(), g.y(), g.w(), g.h());
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Choice<Message> {
     node_id: usize,
@@ -196,6 +491,9 @@ impl<Message: Clone> Choice<Message> {
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Choice<Message> {
     default_impl!();
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        Some(&mut self.tprops)
+    }
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
         None
     }
@@ -210,10 +508,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Choice<Message>
                 b.set_value(self.selected_index);
             }
             let on_change = self.on_change.clone();
+            let sender = dom.emitter();
             b.set_callback(move |b| {
                 let idx = b.value();
                 if let Some(on_change) = &on_change {
-                    app::Sender::<Message>::get().send(on_change(idx));
+                    sender.emit(on_change(idx));
                 }
             });
         });
@@ -233,10 +532,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Choice<Message>
                 b.set_value(self.selected_index);
             }
             let on_change = self.on_change.clone();
+            let sender = dom.emitter();
             b.set_callback(move |b| {
                 let idx = b.value();
                 if let Some(on_change) = &on_change {
-                    app::Sender::<Message>::get().send(on_change(idx));
+                    sender.emit(on_change(idx));
                 }
             });
         });
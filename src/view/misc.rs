@@ -101,6 +101,9 @@ impl<Message> Chart<Message> {
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Chart<Message> {
     default_impl!();
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        Some(&mut self.tprops)
+    }
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
         None
     }
@@ -127,6 +130,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Chart<Message> {
                 b.set_bounds(self.bounds.0, self.bounds.1);
             }
             if self.items != old.items {
+                b.clear();
                 for item in &self.items {
                     b.add(item.value, &item.label, item.col);
                 }
@@ -178,13 +182,25 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Progress<Message
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+struct SpinnerProps {
+    value: f64,
+    minimum: f64,
+    maximum: f64,
+    step: f64,
+}
+
 #[derive(Clone)]
 pub struct Spinner<Message> {
     node_id: usize,
     typ: VNodeType,
     wprops: WidgetProps,
     tprops: TextProps,
-    phantom: PhantomData<Message>,
+    sprops: SpinnerProps,
+    #[allow(clippy::type_complexity)]
+    on_change: Option<std::rc::Rc<Box<dyn Fn(f64) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_submit: Option<std::rc::Rc<Box<dyn Fn(f64) -> Message>>>,
 }
 
 impl<Message> Spinner<Message> {
@@ -197,26 +213,101 @@ impl<Message> Spinner<Message> {
                 ..Default::default()
             },
             tprops: TextProps::default(),
-            phantom: PhantomData,
+            sprops: SpinnerProps {
+                value: 1.,
+                minimum: 1.,
+                maximum: 100.,
+                step: 1.,
+            },
+            on_change: None,
+            on_submit: None,
         }
     }
+    pub fn value(mut self, v: f64) -> Self {
+        self.sprops.value = v;
+        self
+    }
+    pub fn minimum(mut self, v: f64) -> Self {
+        self.sprops.minimum = v;
+        self
+    }
+    pub fn maximum(mut self, v: f64) -> Self {
+        self.sprops.maximum = v;
+        self
+    }
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.sprops.minimum = min;
+        self.sprops.maximum = max;
+        self
+    }
+    pub fn step(mut self, v: f64) -> Self {
+        self.sprops.step = v;
+        self
+    }
+    /// Called whenever the value is changed with the arrows or committed from the input.
+    pub fn on_change<F: 'static + Fn(f64) -> Message>(mut self, f: F) -> Self {
+        self.on_change = Some(std::rc::Rc::new(Box::new(f)));
+        self
+    }
+    /// Called when Enter is pressed in the input, instead of `on_change`.
+    pub fn on_submit<F: 'static + Fn(f64) -> Message>(mut self, f: F) -> Self {
+        self.on_submit = Some(std::rc::Rc::new(Box::new(f)));
+        self
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> Spinner<Message> {
+    fn set_callback(&self, b: &mut misc::Spinner, dom: &VirtualDom<Message>) {
+        let on_change = self.on_change.clone();
+        let on_submit = self.on_submit.clone();
+        let sender = dom.emitter();
+        b.set_callback(move |b| {
+            let v = b.value();
+            let submit =
+                app::event() == enums::Event::KeyDown && app::event_key() == enums::Key::Enter;
+            if let Some(on_submit) = on_submit.as_ref().filter(|_| submit) {
+                sender.emit(on_submit(v));
+            } else if let Some(on_change) = on_change.as_ref().filter(|_| !submit) {
+                sender.emit(on_change(v));
+            }
+        });
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Spinner<Message> {
     default_impl!();
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        Some(&mut self.tprops)
+    }
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
         None
     }
     fn mount(&self, dom: &VirtualDom<Message>) {
         let mut b = misc::Spinner::default();
-        set_tprops!(b, self.tprops);
-        default_mount!(b, self, dom, Spinner);
+        default_mount!(b, self, dom, Spinner, {
+            set_tprops!(b, self.tprops);
+            b.set_range(self.sprops.minimum, self.sprops.maximum);
+            b.set_step(self.sprops.step);
+            b.set_value(self.sprops.value);
+            self.set_callback(&mut b, dom);
+        });
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
         let b;
         default_patch!(b, self, old, dom, Spinner, {
             let old: &Spinner<Message> = old.as_any().downcast_ref().unwrap();
             update_tprops!(b, self.tprops, old.tprops);
+            let (oldp, newp) = (&old.sprops, &self.sprops);
+            if oldp.minimum != newp.minimum || oldp.maximum != newp.maximum {
+                b.set_range(newp.minimum, newp.maximum);
+            }
+            if oldp.step != newp.step {
+                b.set_step(newp.step);
+            }
+            if oldp.value != newp.value && b.value() != newp.value {
+                b.set_value(newp.value);
+            }
+            self.set_callback(b, dom);
         });
     }
 }
@@ -246,6 +337,9 @@ impl<Message> HelpView<Message> {
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for HelpView<Message> {
     default_impl!();
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        Some(&mut self.tprops)
+    }
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
         None
     }
@@ -308,11 +402,12 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for ColorChooser<Mes
             let (r, g, bcol) = self.value.to_rgb();
             let _ = b.set_rgb(r, g, bcol);
             let on_change = self.on_change.clone();
+            let sender = dom.emitter();
             b.set_callback(move |c| {
                 let (rr, gg, bb) = c.rgb_color();
                 if let Some(on_change) = &on_change {
                     let col = enums::Color::from_rgb(rr, gg, bb);
-                    app::Sender::<Message>::get().send(on_change(col));
+                    sender.emit(on_change(col));
                 }
             });
         });
@@ -327,11 +422,12 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for ColorChooser<Mes
             }
             if self.on_change.is_some() != old.on_change.is_some() {
                 let on_change = self.on_change.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |c| {
                     let (rr, gg, bb) = c.rgb_color();
                     if let Some(on_change) = &on_change {
                         let col = enums::Color::from_rgb(rr, gg, bb);
-                        app::Sender::<Message>::get().send(on_change(col));
+                        sender.emit(on_change(col));
                     }
                 });
             }
@@ -345,7 +441,12 @@ pub struct InputChoice<Message> {
     typ: VNodeType,
     wprops: WidgetProps,
     tprops: TextProps,
-    phantom: PhantomData<Message>,
+    items: Vec<String>,
+    value: String,
+    #[allow(clippy::type_complexity)]
+    on_change: Option<std::rc::Rc<Box<dyn Fn(String) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_submit: Option<std::rc::Rc<Box<dyn Fn(String) -> Message>>>,
 }
 
 impl<Message> InputChoice<Message> {
@@ -358,26 +459,91 @@ impl<Message> InputChoice<Message> {
                 ..Default::default()
             },
             tprops: TextProps::default(),
-            phantom: PhantomData,
+            items: vec![],
+            value: String::new(),
+            on_change: None,
+            on_submit: None,
         }
     }
+    /// Sets the entries of the menu.
+    pub fn items(mut self, items: &[&str]) -> Self {
+        self.items = items.iter().map(|s| s.to_string()).collect();
+        self
+    }
+    pub fn value(mut self, value: &str) -> Self {
+        self.value = value.to_string();
+        self
+    }
+    /// Called when the text is edited or an item is picked from the menu.
+    pub fn on_change<F: 'static + Fn(String) -> Message>(mut self, f: F) -> Self {
+        self.on_change = Some(std::rc::Rc::new(Box::new(f)));
+        self
+    }
+    /// Called when Enter is pressed in the input.
+    pub fn on_submit<F: 'static + Fn(String) -> Message>(mut self, f: F) -> Self {
+        self.on_submit = Some(std::rc::Rc::new(Box::new(f)));
+        self
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> InputChoice<Message> {
+    fn set_callback(&self, b: &mut misc::InputChoice, dom: &VirtualDom<Message>) {
+        let on_change = self.on_change.clone();
+        let on_submit = self.on_submit.clone();
+        let sender = dom.emitter();
+        b.set_callback(move |b| {
+            let v = b.value().unwrap_or_default();
+            let submit =
+                app::event() == enums::Event::KeyDown && app::event_key() == enums::Key::Enter;
+            if let Some(on_submit) = on_submit.as_ref().filter(|_| submit) {
+                sender.emit(on_submit(v));
+            } else if let Some(on_change) = on_change.as_ref().filter(|_| !submit) {
+                sender.emit(on_change(v));
+            }
+        });
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for InputChoice<Message> {
     default_impl!();
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        Some(&mut self.tprops)
+    }
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
         None
     }
     fn mount(&self, dom: &VirtualDom<Message>) {
         let mut b = misc::InputChoice::default();
-        set_tprops!(b, self.tprops);
-        default_mount!(b, self, dom, InputChoice);
+        default_mount!(b, self, dom, InputChoice, {
+            set_tprops!(b, self.tprops);
+            for it in &self.items {
+                b.add(it);
+            }
+            b.set_value(&self.value);
+            // Report every edit, and Enter even when the text is unchanged
+            b.input().set_trigger(
+                enums::CallbackTrigger::Changed | enums::CallbackTrigger::EnterKeyAlways,
+            );
+            b.set_trigger(enums::CallbackTrigger::Changed | enums::CallbackTrigger::NotChanged);
+            self.set_callback(&mut b, dom);
+        });
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
         let b;
         default_patch!(b, self, old, dom, InputChoice, {
             let old: &InputChoice<Message> = old.as_any().downcast_ref().unwrap();
             update_tprops!(b, self.tprops, old.tprops);
+            if self.items != old.items {
+                b.clear();
+                for it in &self.items {
+                    b.add(it);
+                }
+            }
+            // Leave the input alone when it already shows the value, to keep its cursor
+            if self.value != old.value && b.value().as_deref() != Some(self.value.as_str()) {
+                b.set_value(&self.value);
+            }
+            self.set_callback(b, dom);
         });
     }
 }
@@ -1,11 +1,14 @@
 mod browser;
 mod button;
+mod canvas;
 mod frame;
 mod group;
 mod input;
+mod map;
 mod menu;
 mod misc;
 mod output;
+mod plot;
 mod table;
 mod text;
 mod tree;
@@ -14,12 +17,15 @@ mod window;
 
 pub use browser::*;
 pub use button::*;
+pub use canvas::*;
 pub use frame::*;
 pub use group::*;
 pub use input::*;
+pub use map::*;
 pub use menu::*;
 pub use misc::*;
 pub use output::*;
+pub use plot::*;
 pub use table::*;
 pub use text::*;
 pub use tree::*;
@@ -40,6 +40,9 @@ macro_rules! define_output {
 
         impl<Message: Clone + 'static + Send + Sync> VNode<Message> for $name<Message> {
             default_impl!();
+            fn tprops(&mut self) -> Option<&mut TextProps> {
+                Some(&mut self.tprops)
+            }
             fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
                 None
             }
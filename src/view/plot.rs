@@ -0,0 +1,437 @@
+use crate::props::*;
+use crate::utils::macros::*;
+use crate::vdom::VirtualDom;
+use crate::vnode::{VNode, VNodeType, View};
+use crate::widgets::{IsWidget, WidgetUnion};
+use fltk::enums::{Align, Color, Font};
+use fltk::{draw, frame, prelude::*};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+const PALETTE: [Color; 6] = [
+    Color::Blue,
+    Color::Red,
+    Color::DarkGreen,
+    Color::DarkMagenta,
+    Color::DarkYellow,
+    Color::DarkCyan,
+];
+
+/// How the points of a `Series` are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    /// Points joined by a line.
+    #[default]
+    Line,
+    /// Unjoined markers at each point.
+    Scatter,
+}
+
+/// A named line or set of points of a `Plot`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    name: String,
+    color: Option<Color>,
+    kind: SeriesKind,
+    points: Vec<(f64, f64)>,
+}
+
+impl Series {
+    pub fn new(name: &str, points: &[(f64, f64)]) -> Self {
+        Self {
+            name: name.to_string(),
+            color: None,
+            kind: SeriesKind::Line,
+            points: points.to_vec(),
+        }
+    }
+    pub fn from_values<I: IntoIterator<Item = f64>>(name: &str, values: I) -> Self {
+        Self {
+            name: name.to_string(),
+            color: None,
+            kind: SeriesKind::Line,
+            points: values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (i as f64, v))
+                .collect(),
+        }
+    }
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+    pub fn kind(mut self, kind: SeriesKind) -> Self {
+        self.kind = kind;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PlotOpts {
+    x_range: Option<(f64, f64)>,
+    y_range: Option<(f64, f64)>,
+    ticks: (usize, usize),
+    grid: bool,
+    legend: bool,
+}
+
+struct SeriesData {
+    name: String,
+    color: Color,
+    kind: SeriesKind,
+    points: VecDeque<(f64, f64)>,
+}
+
+struct PlotState {
+    opts: PlotOpts,
+    series: Vec<SeriesData>,
+    // The extent of the data on each axis, kept up to date with the points
+    x_data: Option<(f64, f64)>,
+    y_data: Option<(f64, f64)>,
+}
+
+impl PlotState {
+    fn new(opts: PlotOpts, series: Vec<SeriesData>) -> Self {
+        let mut st = Self {
+            opts,
+            series,
+            x_data: None,
+            y_data: None,
+        };
+        st.fit_data();
+        st
+    }
+    fn fit_data(&mut self) {
+        self.x_data = data_range(&self.series, |p| p.0);
+        self.y_data = data_range(&self.series, |p| p.1);
+    }
+}
+
+#[derive(Clone)]
+struct PlotWidget {
+    f: frame::Frame,
+    state: Rc<RefCell<PlotState>>,
+}
+
+impl IsWidget for PlotWidget {
+    fn as_widget(&self) -> fltk::widget::Widget {
+        unsafe { self.f.into_widget() }
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// A plot of one or more line or scatter series, with axes, optional grid lines and a legend.
+///
+/// When a series only gained points at its end, or dropped some from its start as
+/// a sliding window does, only those points are applied to the mounted plot.
+#[derive(Clone)]
+pub struct Plot<Message> {
+    node_id: usize,
+    typ: VNodeType,
+    wprops: WidgetProps,
+    series: Vec<Series>,
+    opts: PlotOpts,
+    phantom: PhantomData<Message>,
+}
+
+impl<Message> Plot<Message> {
+    pub fn new(series: &[Series]) -> Self {
+        Self {
+            node_id: 0,
+            typ: VNodeType::Other(std::any::TypeId::of::<PlotWidget>()),
+            wprops: WidgetProps::default(),
+            series: series.to_vec(),
+            opts: PlotOpts {
+                x_range: None,
+                y_range: None,
+                ticks: (5, 5),
+                grid: true,
+                legend: true,
+            },
+            phantom: PhantomData,
+        }
+    }
+    /// Fixes the range of the x axis, which otherwise fits the data.
+    pub fn x_range(mut self, min: f64, max: f64) -> Self {
+        self.opts.x_range = Some((min, max));
+        self
+    }
+    /// Fixes the range of the y axis, which otherwise fits the data.
+    pub fn y_range(mut self, min: f64, max: f64) -> Self {
+        self.opts.y_range = Some((min, max));
+        self
+    }
+    /// Sets the approximate number of ticks on the x and y axes.
+    pub fn ticks(mut self, x: usize, y: usize) -> Self {
+        self.opts.ticks = (x.max(1), y.max(1));
+        self
+    }
+    pub fn grid(mut self, flag: bool) -> Self {
+        self.opts.grid = flag;
+        self
+    }
+    pub fn legend(mut self, flag: bool) -> Self {
+        self.opts.legend = flag;
+        self
+    }
+
+    fn series_data(&self) -> Vec<SeriesData> {
+        self.series
+            .iter()
+            .enumerate()
+            .map(|(i, s)| SeriesData {
+                name: s.name.clone(),
+                color: s.color.unwrap_or(PALETTE[i % PALETTE.len()]),
+                kind: s.kind,
+                points: s.points.iter().copied().collect(),
+            })
+            .collect()
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Plot<Message> {
+    default_impl!();
+    fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
+        None
+    }
+    fn mount(&self, dom: &VirtualDom<Message>) {
+        let mut f = frame::Frame::default();
+        set_wprops(&mut f, &self.wprops);
+        let state = Rc::new(RefCell::new(PlotState::new(
+            self.opts.clone(),
+            self.series_data(),
+        )));
+        f.draw({
+            let state = state.clone();
+            move |f| draw_plot(f, &state.borrow())
+        });
+        dom.widget_map.borrow_mut().insert(
+            self.node_id,
+            WidgetUnion::Other(Rc::new(PlotWidget { f, state })),
+        );
+    }
+    fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
+        if self.typ != *old.typ() {
+            crate::utils::subtree::replace_subtree(old, self, dom);
+            return;
+        }
+        self.set_node_id(old.node_id());
+        let mut map = dom.widget_map.borrow_mut();
+        if let Some(WidgetUnion::Other(ref mut w)) = map.get_mut(&old.node_id()) {
+            update_wprops(&mut w.as_widget(), old.wprops(), &self.wprops);
+            let old: &Plot<Message> = old.as_any().downcast_ref().unwrap();
+            let mut w = w.as_any().downcast_ref::<PlotWidget>().unwrap().clone();
+            let mut st = w.state.borrow_mut();
+            let opts_changed = self.opts != old.opts;
+            if opts_changed {
+                st.opts = self.opts.clone();
+            }
+            let same_shape = self.series.len() == old.series.len()
+                && self
+                    .series
+                    .iter()
+                    .zip(&old.series)
+                    .all(|(n, o)| n.name == o.name && n.color == o.color && n.kind == o.kind);
+            let mut data_changed = !same_shape;
+            if !same_shape {
+                st.series = self.series_data();
+            } else {
+                for ((new, old), data) in self.series.iter().zip(&old.series).zip(&mut st.series) {
+                    data_changed |= apply_points(&mut data.points, &old.points, &new.points);
+                }
+            }
+            if data_changed {
+                st.fit_data();
+            }
+            if opts_changed || data_changed {
+                w.f.redraw();
+            }
+        }
+    }
+}
+
+/// Brings `data`, which holds `old`, up to `new` while reusing the points they share,
+/// returning whether anything changed.
+///
+/// Only the first point of `new` is looked up in `old`, so a series that dropped points
+/// from its start and gained some at its end is checked in linear time. Anything else,
+/// or a window whose first point also occurs earlier, is rebuilt from `new`.
+fn apply_points(data: &mut VecDeque<(f64, f64)>, old: &[(f64, f64)], new: &[(f64, f64)]) -> bool {
+    let shift = new
+        .first()
+        .and_then(|first| old.iter().position(|p| p == first))
+        .filter(|&k| new.len() >= old.len() - k && old[k..] == new[..old.len() - k]);
+    match shift {
+        Some(k) => {
+            let kept = old.len() - k;
+            if k == 0 && kept == new.len() {
+                return false;
+            }
+            data.drain(..k);
+            data.extend(&new[kept..]);
+        }
+        None if old.is_empty() && new.is_empty() => return false,
+        None => {
+            data.clear();
+            data.extend(new);
+        }
+    }
+    true
+}
+
+fn data_range(series: &[SeriesData], f: impl Fn(&(f64, f64)) -> f64) -> Option<(f64, f64)> {
+    series
+        .iter()
+        .flat_map(|s| s.points.iter())
+        .map(f)
+        .filter(|v| v.is_finite())
+        .fold(None, |acc, v| match acc {
+            None => Some((v, v)),
+            Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+        })
+}
+
+/// Picks a step of 1, 2 or 5 times a power of ten giving about `n` ticks over the range.
+fn tick_step(lo: f64, hi: f64, n: usize) -> f64 {
+    let raw = (hi - lo) / n as f64;
+    let mag = 10f64.powf(raw.log10().floor());
+    let norm = raw / mag;
+    let nice = if norm < 1.5 {
+        1.
+    } else if norm < 3. {
+        2.
+    } else if norm < 7. {
+        5.
+    } else {
+        10.
+    };
+    nice * mag
+}
+
+/// Resolves an axis range, widening automatic ranges to whole ticks.
+fn axis(fixed: Option<(f64, f64)>, data: Option<(f64, f64)>, n: usize) -> (f64, f64, f64) {
+    let (mut lo, mut hi) = fixed.or(data).unwrap_or((0., 1.));
+    if hi <= lo {
+        lo -= 0.5;
+        hi = lo + 1.;
+    }
+    let step = tick_step(lo, hi, n);
+    if fixed.is_none() {
+        lo = (lo / step).floor() * step;
+        hi = (hi / step).ceil() * step;
+    }
+    (lo, hi, step)
+}
+
+fn tick_values(lo: f64, hi: f64, step: f64) -> impl Iterator<Item = f64> {
+    let first = (lo / step).ceil() as i64;
+    let last = (hi / step + 1e-9).floor() as i64;
+    (first..=last).map(move |i| i as f64 * step)
+}
+
+fn tick_label(v: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.) as usize;
+    format!("{:.*}", decimals, v)
+}
+
+fn draw_plot(f: &frame::Frame, st: &PlotState) {
+    let opts = &st.opts;
+    draw::set_font(Font::Helvetica, 11);
+    let (x, y, w, h) = (f.x() + 50, f.y() + 10, f.w() - 60, f.h() - 35);
+    if w <= 0 || h <= 0 {
+        return;
+    }
+    let (x0, x1, xstep) = axis(opts.x_range, st.x_data, opts.ticks.0);
+    let (y0, y1, ystep) = axis(opts.y_range, st.y_data, opts.ticks.1);
+    let px = |v: f64| x as f64 + (v - x0) / (x1 - x0) * w as f64;
+    let py = |v: f64| (y + h) as f64 - (v - y0) / (y1 - y0) * h as f64;
+
+    draw::draw_rect_fill(x, y, w, h, Color::Background2);
+    let grid = Color::color_average(Color::Foreground, Color::Background2, 0.15);
+    for v in tick_values(x0, x1, xstep) {
+        let tx = px(v) as i32;
+        if opts.grid {
+            draw::set_draw_color(grid);
+            draw::draw_line(tx, y, tx, y + h);
+        }
+        draw::set_draw_color(Color::Foreground);
+        draw::draw_line(tx, y + h, tx, y + h + 4);
+        draw::draw_text2(
+            &tick_label(v, xstep),
+            tx - 30,
+            y + h + 5,
+            60,
+            15,
+            Align::Center,
+        );
+    }
+    for v in tick_values(y0, y1, ystep) {
+        let ty = py(v) as i32;
+        if opts.grid {
+            draw::set_draw_color(grid);
+            draw::draw_line(x, ty, x + w, ty);
+        }
+        draw::set_draw_color(Color::Foreground);
+        draw::draw_line(x - 4, ty, x, ty);
+        draw::draw_text2(&tick_label(v, ystep), f.x(), ty - 8, 44, 16, Align::Right);
+    }
+    draw::set_draw_color(Color::Foreground);
+    draw::draw_rect(x, y, w, h);
+
+    draw::push_clip(x, y, w, h);
+    for s in &st.series {
+        draw::set_draw_color(s.color);
+        match s.kind {
+            SeriesKind::Line => {
+                draw::set_line_style(draw::LineStyle::Solid, 2);
+                draw::begin_line();
+                for (vx, vy) in &s.points {
+                    draw::vertex(px(*vx), py(*vy));
+                }
+                draw::end_line();
+            }
+            SeriesKind::Scatter => {
+                for (vx, vy) in &s.points {
+                    if vx.is_finite() && vy.is_finite() {
+                        draw::draw_pie(px(*vx) as i32 - 3, py(*vy) as i32 - 3, 7, 7, 0., 360.);
+                    }
+                }
+            }
+        }
+    }
+    draw::set_line_style(draw::LineStyle::Solid, 0);
+
+    if opts.legend && !st.series.is_empty() {
+        let lw = st
+            .series
+            .iter()
+            .map(|s| draw::measure(&s.name, false).0)
+            .max()
+            .unwrap_or(0)
+            + 36;
+        let lh = st.series.len() as i32 * 16 + 6;
+        let (lx, ly) = (x + w - lw - 8, y + 8);
+        draw::draw_rect_fill(lx, ly, lw, lh, Color::Background2);
+        draw::set_draw_color(grid);
+        draw::draw_rect(lx, ly, lw, lh);
+        for (i, s) in st.series.iter().enumerate() {
+            let ry = ly + 3 + i as i32 * 16;
+            draw::set_draw_color(s.color);
+            match s.kind {
+                SeriesKind::Line => draw::draw_rectf(lx + 6, ry + 7, 18, 3),
+                SeriesKind::Scatter => draw::draw_pie(lx + 12, ry + 5, 7, 7, 0., 360.),
+            }
+            draw::set_draw_color(Color::Foreground);
+            draw::draw_text2(&s.name, lx + 30, ry, lw - 30, 16, Align::Left);
+        }
+    }
+    draw::pop_clip();
+}
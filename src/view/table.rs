@@ -3,19 +3,45 @@ use crate::utils::macros::*;
 use crate::vdom::VirtualDom;
 use crate::vnode::{VNode, VNodeType, View};
 use crate::widgets::{IsWidget, WidgetUnion};
-use fltk::table;
-use fltk::{prelude::*, *};
-use std::marker::PhantomData;
+use fltk::enums::{Align, CallbackTrigger, Color, Event, FrameType, Key};
+use fltk::prelude::*;
+use fltk::{app, draw, input, table};
+use std::cell::RefCell;
 use std::rc::Rc;
 
+// The width Fl_Table gives the columns of a table whose widths were never set
+const DEFAULT_COL_WIDTH: i32 = 80;
+
+type CellCallback<Message> = Option<Rc<Box<dyn Fn(i32, i32) -> Message>>>;
+
+struct TableState<Message> {
+    headers: Vec<String>,
+    cells: Vec<Vec<String>>,
+    widths: Vec<i32>,
+    sort: Option<(i32, bool)>,
+    editable: bool,
+    editing: (i32, i32),
+    resizing: bool,
+    selection: Vec<i32>,
+    on_cell_click: CellCallback<Message>,
+    #[allow(clippy::type_complexity)]
+    on_select: Option<Rc<Box<dyn Fn(Vec<i32>) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_header_click: Option<Rc<Box<dyn Fn(i32) -> Message>>>,
+    on_col_resize: CellCallback<Message>,
+    #[allow(clippy::type_complexity)]
+    on_edit: Option<Rc<Box<dyn Fn(i32, i32, String) -> Message>>>,
+}
+
 #[derive(Clone)]
-struct SmartTable {
-    st: fltk_table::SmartTable,
+struct DataTable<Message> {
+    t: table::TableRow,
+    state: Rc<RefCell<TableState<Message>>>,
 }
 
-impl IsWidget for SmartTable {
+impl<Message: 'static> IsWidget for DataTable<Message> {
     fn as_widget(&self) -> fltk::widget::Widget {
-        unsafe { self.st.into_widget() }
+        unsafe { self.t.into_widget() }
     }
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -25,6 +51,7 @@ impl IsWidget for SmartTable {
     }
 }
 
+/// A table displaying rows of text, patched cell by cell.
 #[derive(Clone)]
 pub struct Table<Message> {
     node_id: usize,
@@ -32,25 +59,113 @@ pub struct Table<Message> {
     wprops: WidgetProps,
     headers: Vec<String>,
     cells: Vec<Vec<String>>,
-    phantom: PhantomData<Message>,
+    column_widths: Vec<i32>,
+    sort: Option<(i32, bool)>,
+    editable: bool,
+    select_mode: table::TableRowSelectMode,
+    on_cell_click: CellCallback<Message>,
+    #[allow(clippy::type_complexity)]
+    on_select: Option<Rc<Box<dyn Fn(Vec<i32>) -> Message>>>,
+    #[allow(clippy::type_complexity)]
+    on_header_click: Option<Rc<Box<dyn Fn(i32) -> Message>>>,
+    on_col_resize: CellCallback<Message>,
+    #[allow(clippy::type_complexity)]
+    on_edit: Option<Rc<Box<dyn Fn(i32, i32, String) -> Message>>>,
 }
 
-impl<Message: Clone> Table<Message> {
+impl<Message: Clone + 'static> Table<Message> {
     pub fn new(headers: &[&str], cells: &[&[&str]]) -> Self {
-        let headers = headers.iter().map(|s| s.to_string()).collect();
-        let cells = cells
-            .iter()
-            .map(|a| a.iter().map(|s| s.to_string()).collect())
-            .collect();
+        Self::from_rows(headers, cells, |row| {
+            row.iter().map(|s| s.to_string()).collect()
+        })
+    }
+
+    /// Builds the cells from typed rows, `f` returning the text of each column of a row.
+    pub fn from_rows<T, F: Fn(&T) -> Vec<String>>(headers: &[&str], rows: &[T], f: F) -> Self {
         Self {
             node_id: 0,
-            typ: VNodeType::Other(std::any::TypeId::of::<SmartTable>()),
+            typ: VNodeType::Table,
             wprops: WidgetProps::default(),
-            headers,
-            cells,
-            phantom: PhantomData,
+            headers: headers.iter().map(|s| s.to_string()).collect(),
+            cells: rows.iter().map(f).collect(),
+            column_widths: vec![],
+            sort: None,
+            editable: false,
+            select_mode: table::TableRowSelectMode::Single,
+            on_cell_click: None,
+            on_select: None,
+            on_header_click: None,
+            on_col_resize: None,
+            on_edit: None,
         }
     }
+
+    pub fn column_widths(mut self, widths: &[i32]) -> Self {
+        self.column_widths = widths.to_vec();
+        self
+    }
+
+    /// Shows an arrow in the header of the column the rows are sorted by.
+    pub fn sort_indicator(mut self, col: i32, ascending: bool) -> Self {
+        self.sort = Some((col, ascending));
+        self
+    }
+
+    /// Lets the user edit a cell by double-clicking it, see `on_edit`.
+    pub fn editable(mut self, flag: bool) -> Self {
+        self.editable = flag;
+        self
+    }
+
+    pub fn select_mode(mut self, mode: table::TableRowSelectMode) -> Self {
+        self.select_mode = mode;
+        self
+    }
+
+    pub fn on_cell_click<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_cell_click = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    /// Called with the indices of the selected rows.
+    pub fn on_select<F: 'static + Fn(Vec<i32>) -> Message>(mut self, f: F) -> Self {
+        self.on_select = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    pub fn on_header_click<F: 'static + Fn(i32) -> Message>(mut self, f: F) -> Self {
+        self.on_header_click = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    /// Called with the column and its new width once the user is done resizing it.
+    pub fn on_col_resize<F: 'static + Fn(i32, i32) -> Message>(mut self, f: F) -> Self {
+        self.on_col_resize = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    /// Called with the row, column and value of a cell when its edition is committed with Enter.
+    pub fn on_edit<F: 'static + Fn(i32, i32, String) -> Message>(mut self, f: F) -> Self {
+        self.on_edit = Some(Rc::new(Box::new(f)));
+        self
+    }
+
+    fn cols(&self) -> i32 {
+        self.cells
+            .iter()
+            .map(|row| row.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.headers.len()) as i32
+    }
+
+    fn update_callbacks(&self, st: &mut TableState<Message>) {
+        st.on_cell_click = self.on_cell_click.clone();
+        st.on_select = self.on_select.clone();
+        st.on_header_click = self.on_header_click.clone();
+        st.on_col_resize = self.on_col_resize.clone();
+        st.on_edit = self.on_edit.clone();
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Table<Message> {
@@ -59,24 +174,131 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Table<Message> {
         None
     }
     fn mount(&self, dom: &VirtualDom<Message>) {
-        let mut b = fltk_table::SmartTable::default();
-        b.set_opts(fltk_table::TableOpts {
-            rows: self.cells.len() as i32,
-            cols: self.cells[0].len() as i32,
-            ..Default::default()
-        });
-        set_wprops(&mut *b, &self.wprops);
-        for i in 0..self.headers.len() {
-            b.set_col_header_value(i as i32, &self.headers[i]);
+        let mut t = table::TableRow::default();
+        t.begin();
+        let mut inp = input::Input::default();
+        t.end();
+        inp.hide();
+        inp.set_trigger(CallbackTrigger::EnterKey);
+        t.set_type(self.select_mode);
+        t.set_rows(self.cells.len() as i32);
+        t.set_cols(self.cols());
+        t.set_col_header(!self.headers.is_empty());
+        t.set_col_resize(true);
+        for (col, w) in self.column_widths.iter().enumerate() {
+            t.set_col_width(col as i32, *w);
         }
-        for i in 0..self.cells.len() {
-            for j in 0..self.cells[i].len() {
-                b.set_cell_value(i as i32, j as i32, &self.cells[i][j]);
+        set_wprops(&mut t, &self.wprops);
+
+        let state = Rc::new(RefCell::new(TableState {
+            headers: self.headers.clone(),
+            cells: self.cells.clone(),
+            widths: (0..t.cols()).map(|col| t.col_width(col)).collect(),
+            sort: self.sort,
+            editable: self.editable,
+            editing: (0, 0),
+            resizing: false,
+            selection: vec![],
+            on_cell_click: None,
+            on_select: None,
+            on_header_click: None,
+            on_col_resize: None,
+            on_edit: None,
+        }));
+        self.update_callbacks(&mut state.borrow_mut());
+
+        t.draw_cell({
+            let state = state.clone();
+            move |t, ctx, row, col, x, y, w, h| {
+                let st = state.borrow();
+                match ctx {
+                    table::TableContext::StartPage => {
+                        draw::set_font(t.label_font(), t.label_size())
+                    }
+                    table::TableContext::ColHeader => draw_header(t, &st, col, x, y, w, h),
+                    table::TableContext::Cell => {
+                        let value = st
+                            .cells
+                            .get(row as usize)
+                            .and_then(|r| r.get(col as usize))
+                            .map(|s| s.as_str())
+                            .unwrap_or("");
+                        let selected = t.row_selected(row);
+                        draw_cell(t, value, selected, x, y, w, h);
+                    }
+                    _ => (),
+                }
             }
-        }
+        });
+
+        let sender = dom.emitter();
+        inp.set_callback({
+            let state = state.clone();
+            let sender = sender.clone();
+            let mut t = t.clone();
+            move |i| {
+                let msg = {
+                    let mut st = state.borrow_mut();
+                    let (row, col) = st.editing;
+                    if let Some(cell) = st
+                        .cells
+                        .get_mut(row as usize)
+                        .and_then(|r| r.get_mut(col as usize))
+                    {
+                        *cell = i.value();
+                    }
+                    st.on_edit.clone().map(|cb| cb(row, col, i.value()))
+                };
+                i.hide();
+                t.take_focus().ok();
+                t.redraw();
+                if let Some(msg) = msg {
+                    sender.emit(msg);
+                }
+            }
+        });
+        inp.handle(|i, ev| match ev {
+            Event::KeyDown if app::event_key() == Key::Escape => {
+                i.hide();
+                true
+            }
+            Event::Unfocus => {
+                i.hide();
+                false
+            }
+            _ => false,
+        });
+
+        t.handle({
+            let state = state.clone();
+            move |t, ev| {
+                match ev {
+                    Event::Push => {
+                        state.borrow_mut().resizing = matches!(
+                            t.cursor2rowcol(),
+                            Some((_, _, _, flag)) if flag != table::TableResizeFlag::None
+                        );
+                    }
+                    Event::Released => {
+                        for msg in on_release(t, &mut inp, &mut state.borrow_mut()) {
+                            sender.emit(msg);
+                        }
+                    }
+                    // The selection moves with the arrow keys too
+                    Event::KeyUp => {
+                        if let Some(msg) = selection_changed(t, &mut state.borrow_mut()) {
+                            sender.emit(msg);
+                        }
+                    }
+                    _ => (),
+                }
+                false
+            }
+        });
+
         dom.widget_map.borrow_mut().insert(
             self.node_id,
-            WidgetUnion::Other(Rc::new(SmartTable { st: b })),
+            WidgetUnion::Other(Rc::new(DataTable { t, state })),
         );
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
@@ -85,31 +307,180 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Table<Message> {
             return;
         }
         self.set_node_id(old.node_id());
-        {
-            let mut map = dom.widget_map.borrow_mut();
-            if let Some(WidgetUnion::Other(ref mut f)) = map.get_mut(&old.node_id()) {
-                update_wprops(&mut f.as_widget(), old.wprops(), &self.wprops);
-                let old: &Table<Message> = old.as_any().downcast_ref().unwrap();
-                if self.cells != old.cells || self.headers != old.headers {
-                    let mut f = f.as_any().downcast_ref::<SmartTable>().unwrap().clone();
-                    f.st.clear();
-                    f.st.set_opts(fltk_table::TableOpts {
-                        rows: self.cells.len() as i32,
-                        cols: self.cells[0].len() as i32,
-                        ..Default::default()
-                    });
-                    for i in 0..self.cells.len() {
-                        for j in 0..self.cells[i].len() {
-                            f.st.set_cell_value(i as i32, j as i32, &self.cells[i][j]);
-                        }
-                    }
-                    for i in 0..self.headers.len() {
-                        f.st.set_col_header_value(i as i32, &self.headers[i]);
+        let mut map = dom.widget_map.borrow_mut();
+        if let Some(WidgetUnion::Other(ref mut f)) = map.get_mut(&old.node_id()) {
+            update_wprops(&mut f.as_widget(), old.wprops(), &self.wprops);
+            let old: &Table<Message> = old.as_any().downcast_ref().unwrap();
+            let mut f = f
+                .as_any()
+                .downcast_ref::<DataTable<Message>>()
+                .unwrap()
+                .clone();
+            let mut st = f.state.borrow_mut();
+            self.update_callbacks(&mut st);
+            st.editable = self.editable;
+            if self.select_mode != old.select_mode {
+                f.t.set_type(self.select_mode);
+            }
+
+            // Diff against what the widget shows, which includes the user's edits
+            let mut changed = false;
+            if self.cells.len() != st.cells.len() {
+                st.cells.resize(self.cells.len(), vec![]);
+                f.t.set_rows(self.cells.len() as i32);
+                changed = true;
+            }
+            for (new_row, row) in self.cells.iter().zip(st.cells.iter_mut()) {
+                if new_row.len() != row.len() {
+                    row.resize(new_row.len(), String::new());
+                    changed = true;
+                }
+                for (new_cell, cell) in new_row.iter().zip(row.iter_mut()) {
+                    if new_cell != cell {
+                        cell.clone_from(new_cell);
+                        changed = true;
                     }
                 }
             }
+            if self.headers != st.headers || self.sort != st.sort {
+                st.headers.clone_from(&self.headers);
+                st.sort = self.sort;
+                f.t.set_col_header(!self.headers.is_empty());
+                changed = true;
+            }
+            let cols = self.cols();
+            if cols != f.t.cols() {
+                f.t.set_cols(cols);
+                changed = true;
+            }
+            if self.column_widths != old.column_widths {
+                for (col, w) in self.column_widths.iter().enumerate() {
+                    f.t.set_col_width(col as i32, *w);
+                }
+                // Columns whose width is no longer given go back to the default
+                for col in self.column_widths.len()..old.column_widths.len() {
+                    f.t.set_col_width(col as i32, DEFAULT_COL_WIDTH);
+                }
+                changed = true;
+            }
+            if changed {
+                st.widths = (0..f.t.cols()).map(|col| f.t.col_width(col)).collect();
+                f.t.redraw();
+            }
+        }
+    }
+}
+
+fn on_release<Message>(
+    t: &mut table::TableRow,
+    inp: &mut input::Input,
+    st: &mut TableState<Message>,
+) -> Vec<Message> {
+    let mut msgs = vec![];
+    if st.resizing {
+        st.resizing = false;
+        st.widths.resize(t.cols() as usize, 0);
+        for col in 0..t.cols() {
+            let w = t.col_width(col);
+            if st.widths[col as usize] != w {
+                st.widths[col as usize] = w;
+                if let Some(cb) = &st.on_col_resize {
+                    msgs.push(cb(col, w));
+                }
+            }
+        }
+        return msgs;
+    }
+    match t.cursor2rowcol() {
+        Some((table::TableContext::ColHeader, _, col, _)) => {
+            if let Some(cb) = &st.on_header_click {
+                msgs.push(cb(col));
+            }
+        }
+        Some((table::TableContext::Cell, row, col, _)) => {
+            if let Some(cb) = &st.on_cell_click {
+                msgs.push(cb(row, col));
+            }
+            st.selection = selected_rows(t);
+            if let Some(cb) = &st.on_select {
+                msgs.push(cb(st.selection.clone()));
+            }
+            if st.editable && app::event_clicks() {
+                if let Some((x, y, w, h)) = t.find_cell(table::TableContext::Cell, row, col) {
+                    let value = st
+                        .cells
+                        .get(row as usize)
+                        .and_then(|r| r.get(col as usize))
+                        .cloned()
+                        .unwrap_or_default();
+                    st.editing = (row, col);
+                    inp.resize(x, y, w, h);
+                    inp.set_value(&value);
+                    inp.show();
+                    inp.take_focus().ok();
+                }
+            }
+        }
+        _ => (),
+    }
+    msgs
+}
+
+fn selected_rows(t: &mut table::TableRow) -> Vec<i32> {
+    (0..t.rows()).filter(|r| t.row_selected(*r)).collect()
+}
+
+fn selection_changed<Message>(
+    t: &mut table::TableRow,
+    st: &mut TableState<Message>,
+) -> Option<Message> {
+    let selected = selected_rows(t);
+    if selected == st.selection {
+        return None;
+    }
+    st.selection.clone_from(&selected);
+    st.on_select.as_ref().map(|cb| cb(selected))
+}
+
+fn draw_header<Message>(
+    t: &table::TableRow,
+    st: &TableState<Message>,
+    col: i32,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) {
+    let mut label = st.headers.get(col as usize).cloned().unwrap_or_default();
+    if let Some((sorted, ascending)) = st.sort {
+        if sorted == col {
+            label.push_str(if ascending { " @-22>" } else { " @-22<" });
         }
     }
+    draw::push_clip(x, y, w, h);
+    draw::draw_box(FrameType::ThinUpBox, x, y, w, h, Color::FrameDefault);
+    draw::set_draw_color(t.label_color());
+    draw::draw_text2(&label, x, y, w, h, Align::Center);
+    draw::pop_clip();
+}
+
+fn draw_cell(t: &table::TableRow, value: &str, selected: bool, x: i32, y: i32, w: i32, h: i32) {
+    draw::push_clip(x, y, w, h);
+    draw::set_draw_color(if selected {
+        t.selection_color()
+    } else {
+        Color::BackGround2
+    });
+    draw::draw_rectf(x, y, w, h);
+    draw::set_draw_color(if selected {
+        Color::contrast(t.label_color(), t.selection_color())
+    } else {
+        Color::Foreground
+    });
+    draw::draw_text2(value, x + 2, y, w - 4, h, Align::Left);
+    draw::set_draw_color(Color::Light2);
+    draw::draw_rect(x, y, w, h);
+    draw::pop_clip();
 }
 
 #[derive(Clone)]
@@ -148,9 +519,10 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for TableRow<Message
         default_mount!(t, self, dom, TableRow, {
             t.set_rows(self.rows);
             if let Some(cb) = self.on_select.clone() {
+                let sender = dom.emitter();
                 t.set_callback(move |tbl| {
                     let row = tbl.callback_row();
-                    app::Sender::<Message>::get().send(cb(row));
+                    sender.emit(cb(row));
                 });
             }
         });
@@ -164,10 +536,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for TableRow<Message
             }
             if self.on_select.is_some() != old.on_select.is_some() {
                 let cb = self.on_select.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |tbl| {
                     if let Some(cb) = &cb {
                         let row = tbl.callback_row();
-                        app::Sender::<Message>::get().send(cb(row));
+                        sender.emit(cb(row));
                     }
                 });
             }
@@ -1,12 +1,16 @@
 use crate::props::*;
 use crate::utils::macros::*;
-use crate::vdom::VirtualDom;
+use crate::vdom::{Emitter, VirtualDom};
 use crate::vnode::{VNode, VNodeType, View};
 use crate::widgets::WidgetUnion;
 use fltk::{prelude::*, *};
+use std::cell::RefCell;
+use std::ops::Range;
 use std::rc::Rc;
 
-#[derive(Clone, Debug, PartialEq)]
+pub use fltk::text::StyleTableEntry;
+
+#[derive(Clone, Debug)]
 pub enum TextEditorCommand<Message>
 where
     Message: Clone + Send + Sync + 'static,
@@ -14,7 +18,208 @@ where
     Copy,
     Cut,
     Paste,
+    Undo,
+    Redo,
+    SelectAll,
+    /// Selects the next match of the pattern after the cursor, or the previous one before it.
+    Find {
+        pattern: String,
+        backwards: bool,
+        case: bool,
+    },
+    /// Replaces the selection when it matches the pattern, as after a `Find`, or else
+    /// the next match after the cursor.
+    Replace {
+        pattern: String,
+        replacement: String,
+        case: bool,
+    },
+    ReplaceAll {
+        pattern: String,
+        replacement: String,
+        case: bool,
+    },
+    /// Moves the cursor to the start of a line, lines start at 1.
+    GotoLine(i32),
+    InsertAtCursor(String),
     BufLen(fn(usize) -> Message),
+    /// Answers with the start and end of the selection, if any.
+    Selection(fn(Option<(i32, i32)>) -> Message),
+    /// Answers with the line and column of the cursor, both starting at 1.
+    CursorPosition(fn(i32, i32) -> Message),
+    SelectedText(fn(String) -> Message),
+}
+
+impl<Message: Clone + Send + Sync + 'static> PartialEq for TextEditorCommand<Message> {
+    fn eq(&self, other: &Self) -> bool {
+        use std::ptr::fn_addr_eq;
+        use TextEditorCommand::*;
+        match (self, other) {
+            (Copy, Copy) | (Cut, Cut) | (Paste, Paste) => true,
+            (Undo, Undo) | (Redo, Redo) | (SelectAll, SelectAll) => true,
+            (
+                Find {
+                    pattern: p1,
+                    backwards: b1,
+                    case: c1,
+                },
+                Find {
+                    pattern: p2,
+                    backwards: b2,
+                    case: c2,
+                },
+            ) => p1 == p2 && b1 == b2 && c1 == c2,
+            (
+                Replace {
+                    pattern: p1,
+                    replacement: r1,
+                    case: c1,
+                },
+                Replace {
+                    pattern: p2,
+                    replacement: r2,
+                    case: c2,
+                },
+            )
+            | (
+                ReplaceAll {
+                    pattern: p1,
+                    replacement: r1,
+                    case: c1,
+                },
+                ReplaceAll {
+                    pattern: p2,
+                    replacement: r2,
+                    case: c2,
+                },
+            ) => p1 == p2 && r1 == r2 && c1 == c2,
+            (GotoLine(a), GotoLine(b)) => a == b,
+            (InsertAtCursor(a), InsertAtCursor(b)) => a == b,
+            (BufLen(a), BufLen(b)) => fn_addr_eq(*a, *b),
+            (Selection(a), Selection(b)) => fn_addr_eq(*a, *b),
+            (CursorPosition(a), CursorPosition(b)) => fn_addr_eq(*a, *b),
+            (SelectedText(a), SelectedText(b)) => fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+fn run_command<Message: Clone + Send + Sync + 'static>(
+    ed: &mut text::TextEditor,
+    cmd: TextEditorCommand<Message>,
+    sender: &Emitter<Message>,
+) {
+    let Some(mut buf) = ed.buffer() else {
+        return;
+    };
+    match cmd {
+        TextEditorCommand::Copy => ed.copy(),
+        TextEditorCommand::Cut => ed.cut(),
+        TextEditorCommand::Paste => ed.paste(),
+        TextEditorCommand::Undo => ed.undo(),
+        TextEditorCommand::Redo => ed.redo(),
+        TextEditorCommand::SelectAll => buf.select(0, buf.length()),
+        // An empty pattern matches everywhere without moving on
+        TextEditorCommand::Find { pattern, .. }
+        | TextEditorCommand::Replace { pattern, .. }
+        | TextEditorCommand::ReplaceAll { pattern, .. }
+            if pattern.is_empty() => {}
+        TextEditorCommand::Find {
+            pattern,
+            backwards,
+            case,
+        } => {
+            let found = if backwards {
+                let start = buf
+                    .selection_position()
+                    .map_or(ed.insert_position(), |(s, _)| s);
+                buf.search_backward(start - 1, &pattern, case)
+            } else {
+                buf.search_forward(ed.insert_position(), &pattern, case)
+            };
+            if let Some(pos) = found {
+                let end = pos + pattern.len() as i32;
+                buf.select(pos, end);
+                ed.set_insert_position(if backwards { pos } else { end });
+                ed.show_insert_position();
+            }
+        }
+        TextEditorCommand::Replace {
+            pattern,
+            replacement,
+            case,
+        } => {
+            // A match selected by Find is replaced, rather than the next one
+            let selected = buf.selection_position().filter(|_| {
+                let text = buf.selection_text();
+                if case {
+                    text == pattern
+                } else {
+                    text.to_lowercase() == pattern.to_lowercase()
+                }
+            });
+            let found = selected
+                .map(|(s, _)| s)
+                .or_else(|| buf.search_forward(ed.insert_position(), &pattern, case));
+            if let Some(pos) = found {
+                buf.replace(pos, pos + pattern.len() as i32, &replacement);
+                buf.unselect();
+                ed.set_insert_position(pos + replacement.len() as i32);
+                ed.show_insert_position();
+                ed.do_callback();
+            }
+        }
+        TextEditorCommand::ReplaceAll {
+            pattern,
+            replacement,
+            case,
+        } => {
+            // Searching on after the replacement, each match removes the non-empty pattern
+            // and the replacement is never matched again, so the loop always ends
+            let mut start = 0;
+            let mut replaced = false;
+            while let Some(pos) = buf.search_forward(start, &pattern, case) {
+                buf.replace(pos, pos + pattern.len() as i32, &replacement);
+                start = pos + replacement.len() as i32;
+                replaced = true;
+            }
+            if replaced {
+                ed.do_callback();
+            }
+        }
+        TextEditorCommand::GotoLine(n) => {
+            let mut pos = 0;
+            for _ in 1..n {
+                let end = buf.line_end(pos);
+                if end >= buf.length() {
+                    break;
+                }
+                pos = end + 1;
+            }
+            buf.unselect();
+            ed.set_insert_position(pos);
+            ed.show_insert_position();
+        }
+        TextEditorCommand::InsertAtCursor(s) => {
+            ed.insert(&s);
+            ed.do_callback();
+        }
+        TextEditorCommand::BufLen(f) => sender.emit(f(buf.text().len())),
+        TextEditorCommand::Selection(f) => sender.emit(f(buf.selection_position())),
+        TextEditorCommand::CursorPosition(f) => {
+            let pos = ed.insert_position();
+            let start = buf.line_start(pos);
+            let line = buf.count_lines(0, start) + 1;
+            let col = buf
+                .text_range(start, pos)
+                .unwrap_or_default()
+                .chars()
+                .count() as i32
+                + 1;
+            sender.emit(f(line, col));
+        }
+        TextEditorCommand::SelectedText(f) => sender.emit(f(buf.selection_text())),
+    }
 }
 
 #[derive(Default, Clone, Debug, PartialEq)]
@@ -22,6 +227,141 @@ struct DisplayOpts {
     value: String,
     linenumber_width: i32,
     load_path: Option<String>,
+    uncontrolled: bool,
+}
+
+/// Replaces only the part of the buffer which differs from `value`, so that the cursor,
+/// scroll position and undo history survive.
+fn sync_text(buf: &mut text::TextBuffer, value: &str) {
+    let old = buf.text();
+    if old == value {
+        return;
+    }
+    let (a, b) = (old.as_bytes(), value.as_bytes());
+    let mut prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+    let max_suffix = a.len().min(b.len()) - prefix;
+    let mut suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take(max_suffix)
+        .take_while(|(x, y)| x == y)
+        .count();
+    while !old.is_char_boundary(old.len() - suffix) {
+        suffix -= 1;
+    }
+    buf.replace(
+        prefix as i32,
+        (old.len() - suffix) as i32,
+        &value[prefix..value.len() - suffix],
+    );
+}
+
+/// Splits a line into styled tokens, an alternative to a style function for `highlight_with`.
+pub trait Tokenizer {
+    /// Returns the byte ranges of the line's tokens along with their style character,
+    /// 'A' being the first entry of the style table. The rest of the line gets the style 'A'.
+    fn tokens(&self, line: &str) -> Vec<(Range<usize>, char)>;
+
+    fn style_line(&self, line: &str) -> String {
+        let mut style = vec![b'A'; line.len()];
+        for (range, c) in self.tokens(line) {
+            let end = range.end.min(line.len());
+            for s in style.iter_mut().take(end).skip(range.start) {
+                *s = c as u8;
+            }
+        }
+        String::from_utf8(style).unwrap_or_default()
+    }
+}
+
+type StyleFn = Rc<dyn Fn(&str) -> String>;
+
+#[derive(Clone)]
+struct Highlight {
+    styles: Vec<StyleTableEntry>,
+    style_fn: StyleFn,
+}
+
+/// Styles each line of `text`, keeping exactly one ascii style byte per byte of text.
+fn style_text(text: &str, style_fn: &dyn Fn(&str) -> String) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let mut style = style_fn(line).into_bytes();
+            style.resize(line.len(), b'A');
+            for s in style.iter_mut() {
+                if !s.is_ascii_graphic() {
+                    *s = b'A';
+                }
+            }
+            String::from_utf8(style).unwrap_or_default()
+        })
+        .collect();
+    lines.join("A")
+}
+
+/// Highlighting state shared by the successive nodes of a widget, so its style buffer and
+/// modify callback are created once and reused when highlighting is toggled.
+#[derive(Default)]
+struct LiveHighlight {
+    style_fn: Option<StyleFn>,
+    sbuf: Option<text::TextBuffer>,
+}
+
+fn enable_highlight<D: DisplayExt>(
+    d: &mut D,
+    styles: &[StyleTableEntry],
+    live: Rc<RefCell<LiveHighlight>>,
+) {
+    let Some(mut buf) = d.buffer() else {
+        return;
+    };
+    let existing = live.borrow().sbuf.clone();
+    let mut sbuf = match existing {
+        Some(sbuf) => sbuf,
+        None => {
+            let sbuf = text::TextBuffer::default();
+            live.borrow_mut().sbuf = Some(sbuf.clone());
+            let live = live.clone();
+            // Keep the style buffer aligned with the text, restyling only the modified lines.
+            // fltk can't remove a boxed callback, so it only keeps the alignment while
+            // highlighting is off
+            buf.add_modify_callback2(move |buf, pos, ins, del, _, _| {
+                if ins == 0 && del == 0 {
+                    return;
+                }
+                let (style_fn, Some(mut sbuf)) = ({
+                    let live = live.borrow();
+                    (live.style_fn.clone(), live.sbuf.clone())
+                }) else {
+                    return;
+                };
+                if del > 0 {
+                    sbuf.remove(pos, pos + del);
+                }
+                if ins > 0 {
+                    sbuf.insert(pos, &"A".repeat(ins as usize));
+                }
+                let Some(f) = style_fn else {
+                    return;
+                };
+                let start = buf.line_start(pos);
+                let end = buf.line_end(pos + ins);
+                let text = buf.text_range(start, end).unwrap_or_default();
+                sbuf.replace(start, end, &style_text(&text, &*f));
+            });
+            sbuf
+        }
+    };
+    let style_fn = live.borrow().style_fn.clone();
+    if let Some(f) = style_fn {
+        sbuf.set_text(&style_text(&buf.text(), &*f));
+    }
+    d.set_highlight_data(sbuf, styles.to_vec());
 }
 
 macro_rules! define_text {
@@ -36,6 +376,8 @@ macro_rules! define_text {
             #[allow(clippy::type_complexity)]
             change_cb: Option<Rc<Box<dyn Fn(String) -> Message>>>,
             on_command: Option<Rc<Box<dyn Fn(Message) -> Option<TextEditorCommand<Message>>>>>,
+            highlight: Option<Highlight>,
+            live_highlight: Rc<RefCell<LiveHighlight>>,
         }
 
         impl<Message: Clone + Send + Sync + 'static> $name<Message> {
@@ -51,6 +393,8 @@ macro_rules! define_text {
                     tprops: TextProps::default(),
                     change_cb: None,
                     on_command: None,
+                    highlight: None,
+                    live_highlight: Rc::new(RefCell::new(LiveHighlight::default())),
                 }
             }
             pub fn load_file(mut self, path: &str) -> Self {
@@ -70,14 +414,46 @@ macro_rules! define_text {
                 self.on_command = Some(Rc::new(Box::new(f)));
                 self
             }
+            /// Whether changes of the value are applied to the buffer, which is the default.
+            /// Turning it off avoids comparing the whole text on each update for very large
+            /// buffers, the value then only being used when mounting.
+            pub fn controlled(mut self, flag: bool) -> Self {
+                self.iprops.uncontrolled = !flag;
+                self
+            }
             pub fn linenumber_width(mut self, sz: i32) -> Self {
                 self.iprops.linenumber_width = sz;
                 self
             }
+            /// Colours the text using a style table, `f` mapping each line to a string
+            /// holding one style character per byte of the line, 'A' being the first entry
+            /// of the table. Only the modified lines are restyled when the text changes,
+            /// and the whole text when the table changes.
+            pub fn highlight<F: 'static + Fn(&str) -> String>(
+                mut self,
+                styles: &[StyleTableEntry],
+                f: F,
+            ) -> Self {
+                self.highlight = Some(Highlight {
+                    styles: styles.to_vec(),
+                    style_fn: Rc::new(f),
+                });
+                self
+            }
+            pub fn highlight_with<T: 'static + Tokenizer>(
+                self,
+                styles: &[StyleTableEntry],
+                tokenizer: T,
+            ) -> Self {
+                self.highlight(styles, move |line| tokenizer.style_line(line))
+            }
         }
 
         impl<Message: Clone + 'static + Send + Sync> VNode<Message> for $name<Message> {
             default_impl!();
+            fn tprops(&mut self) -> Option<&mut TextProps> {
+                Some(&mut self.tprops)
+            }
             fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
                 None
             }
@@ -91,10 +467,11 @@ macro_rules! define_text {
                     b.buffer().unwrap().set_text(&self.iprops.value);
                 }
                 let change_cb = self.change_cb.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |b| {
                     let v = b.buffer().unwrap().text();
                     if let Some(change_cb) = &change_cb {
-                        app::Sender::<Message>::get().send(change_cb(v.clone()));
+                        sender.emit(change_cb(v.clone()));
                     }
                 });
                 let ed = b.clone();
@@ -102,28 +479,17 @@ macro_rules! define_text {
                     set_wprops(&mut b, &self.wprops);
                     set_tprops!(b, self.tprops);
                     b.set_linenumber_width(self.iprops.linenumber_width);
+                    if let Some(h) = &self.highlight {
+                        self.live_highlight.borrow_mut().style_fn = Some(h.style_fn.clone());
+                        enable_highlight(&mut b, &h.styles, self.live_highlight.clone());
+                    }
                 });
                 if let Some(ed) = text::TextEditor::from_dyn_widget(&ed) {
                     if let Some(command_handler) = self.on_command.clone() {
+                        let sender = dom.emitter();
                         dom.subscribe_owned(self.node_id, move |msg| {
-                            match command_handler(msg.clone()) {
-                                Some(TextEditorCommand::Copy) => {
-                                    ed.copy();
-                                }
-                                Some(TextEditorCommand::Cut) => {
-                                    ed.cut();
-                                }
-                                Some(TextEditorCommand::Paste) => {
-                                    ed.paste();
-                                }
-                                Some(TextEditorCommand::BufLen(s)) => {
-                                    app::Sender::<Message>::get().send(s(ed
-                                        .buffer()
-                                        .unwrap()
-                                        .text()
-                                        .len()));
-                                }
-                                _ => (),
+                            if let Some(cmd) = command_handler(msg.clone()) {
+                                run_command(&mut ed.clone(), cmd, &sender);
                             }
                         });
                     }
@@ -143,9 +509,35 @@ macro_rules! define_text {
                             }
                         }
                     }
+                    if newi.load_path.is_none() && !newi.uncontrolled && oldi.value != newi.value {
+                        if let Some(mut buf) = b.buffer() {
+                            sync_text(&mut buf, &newi.value);
+                        }
+                    }
                     if oldi.linenumber_width != newi.linenumber_width {
                         b.set_linenumber_width(self.iprops.linenumber_width);
                     }
+                    self.live_highlight = old.live_highlight.clone();
+                    self.live_highlight.borrow_mut().style_fn =
+                        self.highlight.as_ref().map(|h| h.style_fn.clone());
+                    match (&old.highlight, &self.highlight) {
+                        (None, Some(h)) => {
+                            enable_highlight(b, &h.styles, self.live_highlight.clone())
+                        }
+                        (Some(_), None) => {
+                            if let (Some(buf), Some(mut sbuf)) = (b.buffer(), b.style_buffer()) {
+                                sbuf.set_text(&"A".repeat(buf.length() as usize));
+                                b.unset_highlight_data(sbuf);
+                            }
+                        }
+                        (Some(o), Some(h)) if o.styles != h.styles => {
+                            if let (Some(buf), Some(mut sbuf)) = (b.buffer(), b.style_buffer()) {
+                                sbuf.set_text(&style_text(&buf.text(), &*h.style_fn));
+                                b.set_highlight_data(sbuf, h.styles.clone());
+                            }
+                        }
+                        _ => (),
+                    }
                 });
             }
         }
@@ -1,25 +1,102 @@
+use crate::image::{Image, IMAGES};
 use crate::props::*;
 use crate::utils::macros::*;
-use crate::vdom::VirtualDom;
+use crate::vdom::{Emitter, VirtualDom};
 use crate::vnode::{VNode, VNodeType, View};
 use crate::widgets::WidgetUnion;
+use fltk::enums::Event;
 use fltk::prelude::*;
-use fltk::tree;
 use fltk::tree::TreeReason;
-use fltk::*;
-use std::marker::PhantomData;
+use fltk::{button, draw, group, misc, tree};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TreeItem {
     pub label: String,
+    pub key: String,
+    pub children: Vec<TreeItem>,
+    pub icon: Option<Image>,
+    pub checked: Option<bool>,
+    pub tooltip: Option<String>,
 }
 
 impl TreeItem {
+    /// Creates an item whose key is its label. A top-level label may be a path like "a/b".
     pub fn new(label: &str) -> Self {
         Self {
             label: label.to_string(),
+            key: label.to_string(),
+            children: vec![],
+            icon: None,
+            checked: None,
+            tooltip: None,
         }
     }
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = key.to_string();
+        self
+    }
+    pub fn children(mut self, children: &[TreeItem]) -> Self {
+        self.children = children.to_vec();
+        self
+    }
+    pub fn child(mut self, child: TreeItem) -> Self {
+        self.children.push(child);
+        self
+    }
+    pub fn icon(mut self, icon: Image) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+    /// Shows a checkbox next to the item, see `Tree::on_check`.
+    pub fn checked(mut self, flag: bool) -> Self {
+        self.checked = Some(flag);
+        self
+    }
+    pub fn tooltip(mut self, tip: &str) -> Self {
+        self.tooltip = Some(tip.to_string());
+        self
+    }
+}
+
+/// Identifies the item a tree callback was triggered for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    pub key: String,
+    pub path: String,
+}
+
+type TreeCallback<Message> = Option<Rc<Box<dyn Fn(TreeNode) -> Message>>>;
+
+/// An item given to `Tree::selected` or `Tree::open` and their key-based variants.
+#[derive(Debug, Clone, PartialEq)]
+enum ItemRef {
+    Path(String),
+    Key(String),
+}
+
+/// The fltk item a `TreeItem` is mounted as, along with those of its children.
+struct MountedItem {
+    it: tree::TreeItem,
+    children: Vec<MountedItem>,
+}
+
+struct TreeState<Message> {
+    mounted: Vec<MountedItem>,
+    // Keyed by the address of the fltk item
+    nodes: HashMap<usize, TreeNode>,
+    tooltips: HashMap<usize, Rc<CStr>>,
+    // The text of the tooltip last handed to fltk, kept alive while fltk may point to it
+    shown_tip: Option<Rc<CStr>>,
+    checks: HashMap<usize, button::CheckButton>,
+    on_select: TreeCallback<Message>,
+    on_open: TreeCallback<Message>,
+    on_close: TreeCallback<Message>,
+    #[allow(clippy::type_complexity)]
+    on_check: Option<Rc<Box<dyn Fn(TreeNode, bool) -> Message>>>,
 }
 
 #[derive(Clone)]
@@ -28,18 +105,17 @@ pub struct Tree<Message> {
     typ: VNodeType,
     wprops: WidgetProps,
     items: Vec<TreeItem>,
-    selected: Option<String>,
-    #[allow(clippy::type_complexity)]
-    on_select: Option<std::rc::Rc<Box<dyn Fn(String) -> Message>>>,
-    #[allow(clippy::type_complexity)]
-    on_open: Option<std::rc::Rc<Box<dyn Fn(String) -> Message>>>,
+    selected: Option<ItemRef>,
+    on_select: TreeCallback<Message>,
+    on_open: TreeCallback<Message>,
+    on_close: TreeCallback<Message>,
     #[allow(clippy::type_complexity)]
-    on_close: Option<std::rc::Rc<Box<dyn Fn(String) -> Message>>>,
-    opened: Vec<String>,
-    phantom: PhantomData<Message>,
+    on_check: Option<Rc<Box<dyn Fn(TreeNode, bool) -> Message>>>,
+    opened: Vec<ItemRef>,
+    state: Rc<RefCell<TreeState<Message>>>,
 }
 
-impl<Message: Clone> Tree<Message> {
+impl<Message: Clone + 'static> Tree<Message> {
     pub fn new(items: &[TreeItem]) -> Self {
         Self {
             node_id: 0,
@@ -50,34 +126,341 @@ impl<Message: Clone> Tree<Message> {
             on_select: None,
             on_open: None,
             on_close: None,
+            on_check: None,
             opened: vec![],
-            phantom: PhantomData,
+            state: Rc::new(RefCell::new(TreeState {
+                mounted: vec![],
+                nodes: HashMap::new(),
+                tooltips: HashMap::new(),
+                shown_tip: None,
+                checks: HashMap::new(),
+                on_select: None,
+                on_open: None,
+                on_close: None,
+                on_check: None,
+            })),
         }
     }
-    pub fn on_select<F: 'static + Fn(String) -> Message>(mut self, f: F) -> Self {
-        self.on_select = Some(std::rc::Rc::new(Box::new(f)));
+    pub fn on_select<F: 'static + Fn(TreeNode) -> Message>(mut self, f: F) -> Self {
+        self.on_select = Some(Rc::new(Box::new(f)));
         self
     }
-    pub fn on_open<F: 'static + Fn(String) -> Message>(mut self, f: F) -> Self {
-        self.on_open = Some(std::rc::Rc::new(Box::new(f)));
+    pub fn on_open<F: 'static + Fn(TreeNode) -> Message>(mut self, f: F) -> Self {
+        self.on_open = Some(Rc::new(Box::new(f)));
         self
     }
-    pub fn on_close<F: 'static + Fn(String) -> Message>(mut self, f: F) -> Self {
-        self.on_close = Some(std::rc::Rc::new(Box::new(f)));
+    pub fn on_close<F: 'static + Fn(TreeNode) -> Message>(mut self, f: F) -> Self {
+        self.on_close = Some(Rc::new(Box::new(f)));
+        self
+    }
+    /// Called when the checkbox of an item created with `TreeItem::checked` is toggled.
+    pub fn on_check<F: 'static + Fn(TreeNode, bool) -> Message>(mut self, f: F) -> Self {
+        self.on_check = Some(Rc::new(Box::new(f)));
         self
     }
     pub fn selected(mut self, path: &str) -> Self {
-        self.selected = Some(path.to_string());
+        self.selected = Some(ItemRef::Path(path.to_string()));
+        self
+    }
+    /// Selects the item with the given key, keys being unique within the tree.
+    pub fn selected_key(mut self, key: &str) -> Self {
+        self.selected = Some(ItemRef::Key(key.to_string()));
         self
     }
     pub fn open(mut self, path: &str) -> Self {
-        self.opened.push(path.to_string());
+        self.opened.push(ItemRef::Path(path.to_string()));
+        self
+    }
+    pub fn open_key(mut self, key: &str) -> Self {
+        self.opened.push(ItemRef::Key(key.to_string()));
         self
     }
     pub fn opened(mut self, paths: &[&str]) -> Self {
-        self.opened = paths.iter().map(|s| s.to_string()).collect();
+        self.opened = paths.iter().map(|s| ItemRef::Path(s.to_string())).collect();
+        self
+    }
+    pub fn opened_keys(mut self, keys: &[&str]) -> Self {
+        self.opened = keys.iter().map(|s| ItemRef::Key(s.to_string())).collect();
         self
     }
+
+    /// Resolves an item to its current path, the one fltk looks items up by.
+    fn path_of(&self, item: &ItemRef) -> Option<String> {
+        match item {
+            ItemRef::Path(path) => Some(path.clone()),
+            ItemRef::Key(key) => self
+                .state
+                .borrow()
+                .nodes
+                .values()
+                .find(|node| node.key == *key)
+                .map(|node| node.path.clone()),
+        }
+    }
+
+    fn update_callbacks(&self) {
+        let mut st = self.state.borrow_mut();
+        st.on_select = self.on_select.clone();
+        st.on_open = self.on_open.clone();
+        st.on_close = self.on_close.clone();
+        st.on_check = self.on_check.clone();
+    }
+
+    fn rebuild(&self, b: &mut tree::Tree, sender: &Emitter<Message>) {
+        hide_tooltip(b);
+        {
+            let mut st = self.state.borrow_mut();
+            for (_, cb) in st.checks.drain() {
+                button::CheckButton::delete(cb);
+            }
+            st.mounted.clear();
+            st.nodes.clear();
+            st.tooltips.clear();
+        }
+        b.clear();
+        let mounted = self
+            .items
+            .iter()
+            .filter_map(|item| self.add_item(b, None, item, sender))
+            .collect();
+        self.state.borrow_mut().mounted = mounted;
+        if let Some(path) = self.selected.as_ref().and_then(|s| self.path_of(s)) {
+            let _ = b.select(&path, false);
+        }
+        for path in self.opened.iter().filter_map(|o| self.path_of(o)) {
+            let _ = b.open(&path, false);
+        }
+    }
+
+    /// Adds `item` at the end of `parent`'s children, or at its path from the root.
+    fn add_item(
+        &self,
+        b: &mut tree::Tree,
+        parent: Option<&tree::TreeItem>,
+        item: &TreeItem,
+        sender: &Emitter<Message>,
+    ) -> Option<MountedItem> {
+        let mut it = match parent {
+            Some(p) => b.add_to_parent(p, &item.label)?,
+            None => b.add(&item.label)?,
+        };
+        let ptr = it.as_ptr() as usize;
+        set_icon(&mut it, &item.icon);
+        let mut st = self.state.borrow_mut();
+        if let Some(checked) = item.checked {
+            let mut cb = button::CheckButton::default();
+            set_check_label(b, &mut cb, &item.label);
+            cb.set_value(checked);
+            // Fl_Tree is an Fl_Group, and item widgets have to be its children
+            let mut grp = unsafe { group::Group::from_widget_ptr(b.as_widget_ptr() as _) };
+            grp.add(&cb);
+            let state = self.state.clone();
+            let sender = sender.clone();
+            cb.set_callback(move |cb| {
+                let msg = {
+                    let st = state.borrow();
+                    match (&st.on_check, st.nodes.get(&ptr)) {
+                        (Some(f), Some(node)) => Some(f(node.clone(), cb.value())),
+                        _ => None,
+                    }
+                };
+                if let Some(msg) = msg {
+                    sender.emit(msg);
+                }
+            });
+            it.set_widget(&cb);
+            st.checks.insert(ptr, cb);
+        }
+        if let Some(tip) = &item.tooltip {
+            st.tooltips.insert(ptr, tooltip_text(tip));
+        }
+        st.nodes.insert(
+            ptr,
+            TreeNode {
+                key: item.key.clone(),
+                path: b.item_pathname(&it).unwrap_or_default(),
+            },
+        );
+        drop(st);
+        let children = item
+            .children
+            .iter()
+            .filter_map(|child| self.add_item(b, Some(&it), child, sender))
+            .collect();
+        Some(MountedItem { it, children })
+    }
+
+    /// Removes a mounted item along with its children and their widgets.
+    fn remove_item(&self, b: &mut tree::Tree, m: MountedItem) {
+        self.forget_item(&m);
+        let _ = b.remove(&m.it);
+    }
+
+    fn forget_item(&self, m: &MountedItem) {
+        let ptr = m.it.as_ptr() as usize;
+        {
+            let mut st = self.state.borrow_mut();
+            st.nodes.remove(&ptr);
+            st.tooltips.remove(&ptr);
+            // Fl_Tree_Item leaves the deletion of its widget to the tree
+            if let Some(cb) = st.checks.remove(&ptr) {
+                button::CheckButton::delete(cb);
+            }
+        }
+        for child in &m.children {
+            self.forget_item(child);
+        }
+    }
+
+    /// Brings the mounted siblings of `old` to `new`, matching items by key. Retained items
+    /// are updated in place, keeping their open and selection state.
+    fn patch_items(
+        &self,
+        b: &mut tree::Tree,
+        parent: Option<&tree::TreeItem>,
+        mounted: Vec<MountedItem>,
+        old: &[TreeItem],
+        new: &[TreeItem],
+        sender: &Emitter<Message>,
+    ) -> Vec<MountedItem> {
+        let mut retained: HashMap<&str, (MountedItem, &TreeItem)> = HashMap::new();
+        for (m, o) in mounted.into_iter().zip(old) {
+            if new.iter().any(|n| n.key == o.key) && !retained.contains_key(o.key.as_str()) {
+                retained.insert(&o.key, (m, o));
+            } else {
+                self.remove_item(b, m);
+            }
+        }
+        let mut items: Vec<MountedItem> = vec![];
+        for n in new {
+            let m = match retained.remove(n.key.as_str()) {
+                // Top-level items moved to another path, or whose checkbox comes or goes,
+                // are re-added
+                Some((m, o))
+                    if (parent.is_some()
+                        || o.label == n.label
+                        || !(o.label.contains('/') || n.label.contains('/')))
+                        && o.checked.is_some() == n.checked.is_some() =>
+                {
+                    Some(self.update_item(b, m, o, n, sender))
+                }
+                Some((m, _)) => {
+                    self.remove_item(b, m);
+                    self.add_item(b, parent, n, sender)
+                }
+                None => self.add_item(b, parent, n, sender),
+            };
+            let Some(mut m) = m else {
+                continue;
+            };
+            // Keep the fltk order of siblings, added items having been appended
+            if let Some(prev) = items.last() {
+                if m.it.parent() == prev.it.parent()
+                    && m.it.prev_sibling().as_ref() != Some(&prev.it)
+                {
+                    let _ = m.it.move_below(&prev.it);
+                }
+            }
+            items.push(m);
+        }
+        items
+    }
+
+    fn update_item(
+        &self,
+        b: &mut tree::Tree,
+        mut m: MountedItem,
+        old: &TreeItem,
+        new: &TreeItem,
+        sender: &Emitter<Message>,
+    ) -> MountedItem {
+        let ptr = m.it.as_ptr() as usize;
+        if old.label != new.label {
+            m.it.set_label(&new.label);
+        }
+        if old.icon != new.icon {
+            set_icon(&mut m.it, &new.icon);
+        }
+        {
+            let mut st = self.state.borrow_mut();
+            if let Some(cb) = st.checks.get_mut(&ptr) {
+                if old.label != new.label {
+                    set_check_label(b, cb, &new.label);
+                }
+                if old.checked != new.checked {
+                    cb.set_value(new.checked.unwrap_or_default());
+                }
+            }
+            if old.tooltip != new.tooltip {
+                match &new.tooltip {
+                    Some(tip) => {
+                        st.tooltips.insert(ptr, tooltip_text(tip));
+                    }
+                    None => {
+                        st.tooltips.remove(&ptr);
+                    }
+                }
+            }
+            if let Some(node) = st.nodes.get_mut(&ptr) {
+                node.key = new.key.clone();
+            }
+        }
+        if old.children != new.children {
+            let children = std::mem::take(&mut m.children);
+            m.children = self.patch_items(
+                b,
+                Some(&m.it),
+                children,
+                &old.children,
+                &new.children,
+                sender,
+            );
+        }
+        m
+    }
+
+    /// Updates the paths reported to callbacks, which change when an ancestor is renamed.
+    fn refresh_paths(&self, b: &tree::Tree, mounted: &[MountedItem]) {
+        for m in mounted {
+            if let Some(node) = self
+                .state
+                .borrow_mut()
+                .nodes
+                .get_mut(&(m.it.as_ptr() as usize))
+            {
+                node.path = b.item_pathname(&m.it).unwrap_or_default();
+            }
+            self.refresh_paths(b, &m.children);
+        }
+    }
+}
+
+fn set_icon(it: &mut tree::TreeItem, icon: &Option<Image>) {
+    match icon {
+        Some(i) => unsafe {
+            it.set_user_icon(Some(fltk::image::Image::from_image_ptr(
+                IMAGES.lock().unwrap().get(&i.idx).unwrap().as_image_ptr(),
+            )));
+        },
+        None => it.set_user_icon(None::<fltk::image::Image>),
+    }
+}
+
+fn set_check_label(b: &tree::Tree, cb: &mut button::CheckButton, label: &str) {
+    cb.set_label(label);
+    let (w, _) = draw::measure(label, true);
+    cb.set_size(w + 30, b.item_label_size() + 8);
+}
+
+fn tooltip_text(tip: &str) -> Rc<CStr> {
+    CString::new(tip).unwrap_or_default().into()
+}
+
+/// Hides an area tooltip shown by the tree, whose item may be changing.
+fn hide_tooltip(b: &tree::Tree) {
+    if misc::Tooltip::current_widget().as_widget_ptr() == b.as_widget_ptr() {
+        misc::Tooltip::enter_area(b, 0, 0, 0, 0, c"");
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Tree<Message> {
@@ -88,122 +471,92 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Tree<Message> {
     fn mount(&self, dom: &VirtualDom<Message>) {
         let mut b = tree::Tree::default();
         default_mount!(b, self, dom, Tree, {
-            for item in &self.items {
-                b.add(&item.label);
-            }
-            if let Some(path) = &self.selected {
-                let _ = b.select(path, true);
-            }
-            if !self.opened.is_empty() {
-                for p in &self.opened {
-                    let _ = b.open(p, true);
-                }
-            }
-            let on_select = self.on_select.clone();
-            let on_open = self.on_open.clone();
-            let on_close = self.on_close.clone();
-            if on_select.is_some() || on_open.is_some() || on_close.is_some() {
-                b.set_callback(move |t| match t.callback_reason() {
-                    TreeReason::Opened => {
-                        if let Some(cb) = &on_open {
-                            if let Some(it) = t.callback_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
-                        }
+            let sender = dom.emitter();
+            self.update_callbacks();
+            self.rebuild(&mut b, &sender);
+            let state = self.state.clone();
+            b.set_callback(move |t| {
+                let Some(it) = t.callback_item() else {
+                    return;
+                };
+                let msg = {
+                    let st = state.borrow();
+                    let cb = match t.callback_reason() {
+                        TreeReason::Opened => &st.on_open,
+                        TreeReason::Closed => &st.on_close,
+                        TreeReason::Selected | TreeReason::Reselected => &st.on_select,
+                        _ => &None,
+                    };
+                    match (cb, st.nodes.get(&(it.as_ptr() as usize))) {
+                        (Some(cb), Some(node)) => Some(cb(node.clone())),
+                        _ => None,
                     }
-                    TreeReason::Closed => {
-                        if let Some(cb) = &on_close {
-                            if let Some(it) = t.callback_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
-                        }
-                    }
-                    TreeReason::Selected | TreeReason::Reselected => {
-                        if let Some(cb) = &on_select {
-                            if let Some(it) = t.first_selected_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
+                };
+                if let Some(msg) = msg {
+                    sender.emit(msg);
+                }
+            });
+            let state = self.state.clone();
+            b.handle(move |t, ev| {
+                if matches!(ev, Event::Enter | Event::Move) {
+                    if let Some(it) = t.find_clicked(true) {
+                        let mut st = state.borrow_mut();
+                        if let Some(tip) = st.tooltips.get(&(it.as_ptr() as usize)).cloned() {
+                            // SAFETY: the text is held by `shown_tip` until fltk was handed
+                            // another one, whatever happens to the item, and fltk forgets
+                            // it when the tree is deleted
+                            let text: &'static CStr = unsafe { &*Rc::as_ptr(&tip) };
+                            let shown = st.shown_tip.replace(tip);
+                            misc::Tooltip::enter_area(t, it.x(), it.y(), it.w(), it.h(), text);
+                            drop(shown);
                         }
                     }
-                    _ => {}
-                });
-            }
+                }
+                false
+            });
         });
     }
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>) {
         let b;
         default_patch!(b, self, old, dom, Tree, {
             let old: &Tree<Message> = old.as_any().downcast_ref().unwrap();
-            let items_changed = self.items != old.items;
-            if items_changed {
-                b.clear();
-                for item in &self.items {
-                    b.add(&item.label);
-                }
+            self.state = old.state.clone();
+            self.update_callbacks();
+            if self.items != old.items {
+                hide_tooltip(b);
+                let mounted = std::mem::take(&mut self.state.borrow_mut().mounted);
+                let mounted =
+                    self.patch_items(b, None, mounted, &old.items, &self.items, &dom.emitter());
+                self.refresh_paths(b, &mounted);
+                self.state.borrow_mut().mounted = mounted;
             }
             if self.selected != old.selected {
-                if let Some(path) = &self.selected {
-                    let _ = b.select(path, true);
-                }
-            }
-            if items_changed || self.opened != old.opened {
-                // Close paths no longer opened
-                for p in old.opened.iter() {
-                    if !self.opened.iter().any(|n| n == p) {
-                        let _ = b.open(p, false);
-                    }
+                if let Some(path) = old.selected.as_ref().and_then(|s| self.path_of(s)) {
+                    let _ = b.deselect(&path, false);
                 }
-                // Open any new paths
-                for p in self.opened.iter() {
-                    if !old.opened.iter().any(|n| n == p) {
-                        let _ = b.open(p, true);
-                    }
+                if let Some(path) = self.selected.as_ref().and_then(|s| self.path_of(s)) {
+                    let _ = b.select(&path, false);
                 }
             }
-            if self.on_select.is_some() != old.on_select.is_some()
-                || self.on_open.is_some() != old.on_open.is_some()
-                || self.on_close.is_some() != old.on_close.is_some()
-            {
-                let on_select = self.on_select.clone();
-                let on_open = self.on_open.clone();
-                let on_close = self.on_close.clone();
-                b.set_callback(move |t| match t.callback_reason() {
-                    TreeReason::Opened => {
-                        if let Some(cb) = &on_open {
-                            if let Some(it) = t.callback_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
+            if self.opened != old.opened {
+                // Close items no longer opened
+                for o in old.opened.iter() {
+                    if !self.opened.contains(o) {
+                        if let Some(path) = self.path_of(o) {
+                            let _ = b.close(&path, false);
                         }
                     }
-                    TreeReason::Closed => {
-                        if let Some(cb) = &on_close {
-                            if let Some(it) = t.callback_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
-                        }
-                    }
-                    TreeReason::Selected | TreeReason::Reselected => {
-                        if let Some(cb) = &on_select {
-                            if let Some(it) = t.first_selected_item() {
-                                if let Some(lbl) = it.label() {
-                                    app::Sender::<Message>::get().send(cb(lbl.to_string()));
-                                }
-                            }
+                }
+                // Open any new items
+                for o in self.opened.iter() {
+                    if !old.opened.contains(o) {
+                        if let Some(path) = self.path_of(o) {
+                            let _ = b.open(&path, false);
                         }
                     }
-                    _ => {}
-                });
+                }
             }
+            b.redraw();
         });
     }
 }
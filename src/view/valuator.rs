@@ -82,10 +82,11 @@ macro_rules! define_valuator {
                     b.set_maximum(self.iprops.maximum);
                     b.set_step(self.iprops.step, 1);
                     let change_cb = self.change_cb.clone();
+                    let sender = dom.emitter();
                     b.set_callback(move |b| {
                         let v = b.value();
                         if let Some(change_cb) = &change_cb {
-                            app::Sender::<Message>::get().send(change_cb(v));
+                            sender.emit(change_cb(v));
                         }
                     });
                 });
@@ -99,10 +100,11 @@ macro_rules! define_valuator {
                     if oldi.value != newi.value {
                         b.set_value(newi.value);
                         let change_cb = self.change_cb.clone();
+                        let sender = dom.emitter();
                         b.set_callback(move |b| {
                             let v = b.value();
                             if let Some(change_cb) = &change_cb {
-                                app::Sender::<Message>::get().send(change_cb(v));
+                                sender.emit(change_cb(v));
                             }
                         });
                     }
@@ -168,6 +170,9 @@ macro_rules! define_valuator_with_tprops {
 
         impl<Message: Clone + 'static + Send + Sync> VNode<Message> for $name<Message> {
             default_impl!();
+            fn tprops(&mut self) -> Option<&mut TextProps> {
+                Some(&mut self.tprops)
+            }
             fn gprops(&mut self) -> Option<&mut GroupProps<Message>> {
                 None
             }
@@ -181,10 +186,11 @@ macro_rules! define_valuator_with_tprops {
                     b.set_maximum(self.iprops.maximum);
                     b.set_step(self.iprops.step, 1);
                     let change_cb = self.change_cb.clone();
+                    let sender = dom.emitter();
                     b.set_callback(move |b| {
                         let v = b.value();
                         if let Some(change_cb) = &change_cb {
-                            app::Sender::<Message>::get().send(change_cb(v));
+                            sender.emit(change_cb(v));
                         }
                     });
                 });
@@ -199,10 +205,11 @@ macro_rules! define_valuator_with_tprops {
                     if oldi.value != newi.value {
                         b.set_value(newi.value);
                         let change_cb = self.change_cb.clone();
+                        let sender = dom.emitter();
                         b.set_callback(move |b| {
                             let v = b.value();
                             if let Some(change_cb) = &change_cb {
-                                app::Sender::<Message>::get().send(change_cb(v));
+                                sender.emit(change_cb(v));
                             }
                         });
                     }
@@ -264,10 +271,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for HorScrollbar<Mes
             b.set_maximum(self.iprops.maximum);
             b.set_step(self.iprops.step, 1);
             let change_cb = self.change_cb.clone();
+            let sender = dom.emitter();
             b.set_callback(move |b| {
                 let v = b.value();
                 if let Some(change_cb) = &change_cb {
-                    app::Sender::<Message>::get().send(change_cb(v));
+                    sender.emit(change_cb(v));
                 }
             });
         });
@@ -281,10 +289,11 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for HorScrollbar<Mes
             if oldi.value != newi.value {
                 b.set_value(newi.value);
                 let change_cb = self.change_cb.clone();
+                let sender = dom.emitter();
                 b.set_callback(move |b| {
                     let v = b.value();
                     if let Some(change_cb) = &change_cb {
-                        app::Sender::<Message>::get().send(change_cb(v));
+                        sender.emit(change_cb(v));
                     }
                 });
             }
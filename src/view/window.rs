@@ -12,6 +12,9 @@ pub struct Window<Message> {
     wprops: WidgetProps,
     gprops: GroupProps<Message>,
     on_close: Option<Message>,
+    resizable: bool,
+    modal: bool,
+    size_range: Option<(i32, i32, i32, i32)>,
 }
 
 impl<Message: Clone> Window<Message> {
@@ -24,12 +27,42 @@ impl<Message: Clone> Window<Message> {
                 children: children.to_vec(),
             },
             on_close: None,
+            resizable: false,
+            modal: false,
+            size_range: None,
         }
     }
+    /// Emitted by the close button and Escape. Without it they do nothing, the window
+    /// being closed once it is no longer in the view, see `Application::windows`.
     pub fn on_close(mut self, msg: Message) -> Self {
         self.on_close = Some(msg);
         self
     }
+    pub fn resizable(mut self, val: bool) -> Self {
+        self.resizable = val;
+        self
+    }
+    pub fn modal(mut self, val: bool) -> Self {
+        self.modal = val;
+        self
+    }
+    pub fn size_range(mut self, min_w: i32, min_h: i32, max_w: i32, max_h: i32) -> Self {
+        self.size_range = Some((min_w, min_h, max_w, max_h));
+        self
+    }
+}
+
+impl<Message: Clone + 'static + Send + Sync> Window<Message> {
+    // Set on every patch, as the messages can't be compared
+    fn set_callback(&self, w: &mut window::Window, dom: &VirtualDom<Message>) {
+        let msg = self.on_close.clone();
+        let sender = dom.emitter();
+        w.set_callback(move |_| {
+            if let Some(msg) = &msg {
+                sender.emit(msg.clone());
+            }
+        });
+    }
 }
 
 impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Window<Message> {
@@ -45,11 +78,16 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Window<Message>
                 child.mount(dom);
             }
             w.end();
-            if let Some(msg) = self.on_close.clone() {
-                w.set_callback(move |_| {
-                    app::Sender::<Message>::get().send(msg.clone());
-                });
+            if self.resizable {
+                w.make_resizable(true);
+            }
+            if self.modal {
+                w.make_modal(true);
+            }
+            if let Some((min_w, min_h, max_w, max_h)) = self.size_range {
+                w.size_range(min_w, min_h, max_w, max_h);
             }
+            self.set_callback(&mut w, dom);
             w.show();
         });
     }
@@ -57,33 +95,48 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Window<Message>
         let b;
         default_patch!(b, self, old, dom, Window, {
             let old: &Window<Message> = old.as_any().downcast_ref().unwrap();
-            if self.on_close.is_some() != old.on_close.is_some() {
-                let msg = self.on_close.clone();
-                b.set_callback(move |_| {
-                    if let Some(msg) = &msg {
-                        app::Sender::<Message>::get().send(msg.clone());
-                    }
-                });
+            if self.resizable != old.resizable {
+                b.make_resizable(self.resizable);
+            }
+            if self.modal != old.modal {
+                b.make_modal(self.modal);
+            }
+            if self.size_range != old.size_range {
+                // 0 lifts the limits
+                let (min_w, min_h, max_w, max_h) = self.size_range.unwrap_or((0, 0, 0, 0));
+                b.size_range(min_w, min_h, max_w, max_h);
             }
+            self.set_callback(b, dom);
         });
         // manual child diff similar to update_group_children!
         let old_id = old.node_id();
         let oldg = old.gprops().unwrap();
         let newg = &mut self.gprops;
+        // Clone the window handle to avoid holding the map borrow while mounting
+        let w_opt = {
+            let mut map = dom.widget_map.borrow_mut();
+            if let Some(WidgetUnion::Window(ref mut w)) = map.get_mut(&old_id) {
+                Some(w.clone())
+            } else {
+                None
+            }
+        };
+        if crate::utils::subtree::is_keyed(&mut oldg.children, &mut newg.children) {
+            if let Some(mut w) = w_opt {
+                crate::utils::subtree::patch_keyed_children(
+                    &mut w,
+                    &mut oldg.children,
+                    &mut newg.children,
+                    dom,
+                );
+            }
+            return;
+        }
         let min_len = oldg.children.len().min(newg.children.len());
         for i in 0..min_len {
             newg.children[i].patch(&mut oldg.children[i], dom);
         }
         if newg.children.len() > oldg.children.len() {
-            // Clone the window handle to avoid holding the map borrow while mounting
-            let w_opt = {
-                let mut map = dom.widget_map.borrow_mut();
-                if let Some(WidgetUnion::Window(ref mut w)) = map.get_mut(&old_id) {
-                    Some(w.clone())
-                } else {
-                    None
-                }
-            };
             if let Some(w) = w_opt {
                 w.begin();
                 for i in oldg.children.len()..newg.children.len() {
@@ -94,7 +147,7 @@ impl<Message: Clone + 'static + Send + Sync> VNode<Message> for Window<Message>
         }
         if oldg.children.len() > newg.children.len() {
             for i in newg.children.len()..oldg.children.len() {
-                crate::utils::subtree::remove_subtree(&mut oldg.children[i], dom);
+                oldg.children[i].unmount(dom);
             }
         }
     }
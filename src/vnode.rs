@@ -1,8 +1,9 @@
 use crate::id::next_id;
 use crate::image::Image;
 use crate::props::*;
+use crate::style::{Style, StyleSheet};
 use crate::vdom::VirtualDom;
-use fltk::enums;
+use fltk::{enums, group};
 use std::any::Any;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -11,6 +12,7 @@ pub enum VNodeType {
     Row,
     Button,
     Frame,
+    Space,
     Input,
     MenuBar,
     RadioButton,
@@ -77,7 +79,6 @@ pub enum VNodeType {
     HelpView,
     Table,
     TableRow,
-    SmartTable,
     Tree,
     Other(std::any::TypeId),
 }
@@ -85,6 +86,21 @@ pub enum VNodeType {
 pub trait HasProps<Message> {
     fn label(self, label: &str) -> Self;
     fn fixed(self, sz: i32) -> Self;
+    /// The smallest size of the node along the axis of its `Column` or `Row`.
+    fn min_size(self, sz: i32) -> Self;
+    /// The largest size of the node along the axis of its `Column` or `Row`.
+    fn max_size(self, sz: i32) -> Self;
+    /// The share of the free space the node takes in its `Column` or `Row`, 1 by default.
+    fn flex(self, weight: i32) -> Self;
+    /// Overrides the cross-axis alignment of the node's `Column` or `Row`. Nodes
+    /// which don't stretch use their `w` in a column and their `h` in a row.
+    fn align_self(self, v: FlexAlign) -> Self;
+    /// Places the node in a cell of its `Grid`, counting from 0.
+    fn cell(self, row: i32, col: i32) -> Self;
+    /// The number of rows and columns the node takes from its `cell`, 1 by default.
+    fn span(self, rows: i32, cols: i32) -> Self;
+    /// How the node is placed in its `cell`, filling it by default.
+    fn grid_align(self, v: group::GridAlign) -> Self;
     fn color(self, col: enums::Color) -> Self;
     fn boxtype(self, boxtype: enums::FrameType) -> Self;
     fn selection_color(self, v: enums::Color) -> Self;
@@ -102,6 +118,13 @@ pub trait HasProps<Message> {
     fn h(self, x: i32) -> Self;
     fn image(self, i: Option<Image>) -> Self;
     fn deimage(self, i: Option<Image>) -> Self;
+    fn key(self, k: &str) -> Self;
+    /// Adds a class of the application's `StyleSheet` to the node.
+    fn class(self, name: &str) -> Self;
+    /// Only applies to nodes showing text of their own, like inputs and menus.
+    fn text_color(self, v: enums::Color) -> Self;
+    fn text_font(self, v: enums::Font) -> Self;
+    fn text_size(self, v: i32) -> Self;
 }
 
 impl<Message: 'static, W: VNode<Message>> HasProps<Message> for W {
@@ -113,6 +136,34 @@ impl<Message: 'static, W: VNode<Message>> HasProps<Message> for W {
         self.wprops().fixed = Some(sz);
         self
     }
+    fn min_size(mut self, sz: i32) -> Self {
+        self.wprops().min_size = Some(sz);
+        self
+    }
+    fn max_size(mut self, sz: i32) -> Self {
+        self.wprops().max_size = Some(sz);
+        self
+    }
+    fn flex(mut self, weight: i32) -> Self {
+        self.wprops().flex = Some(weight);
+        self
+    }
+    fn align_self(mut self, v: FlexAlign) -> Self {
+        self.wprops().align_self = Some(v);
+        self
+    }
+    fn cell(mut self, row: i32, col: i32) -> Self {
+        self.wprops().cell = Some((row, col));
+        self
+    }
+    fn span(mut self, rows: i32, cols: i32) -> Self {
+        self.wprops().span = Some((rows, cols));
+        self
+    }
+    fn grid_align(mut self, v: group::GridAlign) -> Self {
+        self.wprops().grid_align = Some(v);
+        self
+    }
     fn color(mut self, col: enums::Color) -> Self {
         self.wprops().color = Some(col);
         self
@@ -181,6 +232,32 @@ impl<Message: 'static, W: VNode<Message>> HasProps<Message> for W {
         self.wprops().deimage = i;
         self
     }
+    fn key(mut self, k: &str) -> Self {
+        self.wprops().key = Some(k.to_string());
+        self
+    }
+    fn class(mut self, name: &str) -> Self {
+        self.wprops().classes.push(name.to_string());
+        self
+    }
+    fn text_color(mut self, v: enums::Color) -> Self {
+        if let Some(t) = self.tprops() {
+            t.text_color = Some(v);
+        }
+        self
+    }
+    fn text_font(mut self, v: enums::Font) -> Self {
+        if let Some(t) = self.tprops() {
+            t.text_font = Some(v);
+        }
+        self
+    }
+    fn text_size(mut self, v: i32) -> Self {
+        if let Some(t) = self.tprops() {
+            t.text_size = Some(v);
+        }
+        self
+    }
 }
 
 pub type View<Message> = Box<dyn VNode<Message>>;
@@ -193,14 +270,40 @@ pub trait VNode<Message: 'static>: Any + dyn_clone::DynClone {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn gprops(&mut self) -> Option<&mut GroupProps<Message>>;
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        None
+    }
     fn mount(&self, dom: &VirtualDom<Message>);
     fn patch(&mut self, old: &mut View<Message>, dom: &VirtualDom<Message>);
+    fn unmount(&mut self, dom: &VirtualDom<Message>)
+    where
+        Message: Clone + Send + Sync,
+    {
+        crate::utils::subtree::remove_subtree(self, dom);
+    }
     fn view(self) -> View<Message>
     where
         Self: Sized,
     {
         Box::new(self)
     }
+    /// Fills the props the nodes of the subtree don't set from the sheet's styles and
+    /// from the style inherited from the parent.
+    fn apply_styles(&mut self, sheet: &StyleSheet, inherited: &Style) {
+        let typ = self.typ().clone();
+        let mut wprops = std::mem::take(self.wprops());
+        let inherit = sheet.style_node(&typ, &mut wprops, self.tprops(), inherited);
+        *self.wprops() = wprops;
+        if let Some(gprops) = self.gprops() {
+            for child in &mut gprops.children {
+                child.apply_styles(sheet, &inherit);
+            }
+        }
+    }
+    /// The subtree of a node wrapping one with another message type, as `Map` does.
+    fn mapped(&mut self) -> Option<&mut dyn AnyNode> {
+        None
+    }
     fn assign_ids_topdown(&mut self) {
         self.set_node_id(next_id());
 
@@ -212,6 +315,52 @@ pub trait VNode<Message: 'static>: Any + dyn_clone::DynClone {
     }
 }
 
+/// The part of a node which doesn't depend on its message type, so that a view can be walked
+/// through the subtrees of `Map` nodes.
+pub trait AnyNode {
+    fn node_id(&self) -> usize;
+    fn typ(&self) -> &VNodeType;
+    fn wprops(&mut self) -> &mut WidgetProps;
+    fn tprops(&mut self) -> Option<&mut TextProps>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn children(&mut self) -> Vec<&mut dyn AnyNode>;
+}
+
+impl<Message: 'static> AnyNode for View<Message> {
+    fn node_id(&self) -> usize {
+        (**self).node_id()
+    }
+    fn typ(&self) -> &VNodeType {
+        (**self).typ()
+    }
+    fn wprops(&mut self) -> &mut WidgetProps {
+        (**self).wprops()
+    }
+    fn tprops(&mut self) -> Option<&mut TextProps> {
+        (**self).tprops()
+    }
+    fn as_any(&self) -> &dyn Any {
+        (**self).as_any()
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        (**self).as_any_mut()
+    }
+    fn children(&mut self) -> Vec<&mut dyn AnyNode> {
+        if (**self).gprops().is_none() {
+            return (**self).mapped().into_iter().collect();
+        }
+        match (**self).gprops() {
+            Some(gprops) => gprops
+                .children
+                .iter_mut()
+                .map(|ch| ch as &mut dyn AnyNode)
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
 impl<Message> std::clone::Clone for View<Message> {
     fn clone(&self) -> Self {
         dyn_clone::clone_box(&**self)